@@ -0,0 +1,90 @@
+use std::{fs, path::Path};
+
+use aoc2023::{day5, day6, day7, day8, day9, prelude::*};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Benchmarks every input that is available: the puzzle example, and the real
+/// `input/N.txt` when it has been checked out next to the manifest.
+fn inputs(day: u8, example: &'static str) -> Vec<(&'static str, String)> {
+    let mut inputs = vec![("example", example.to_string())];
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("input")
+        .join(format!("{}.txt", day));
+    if let Ok(real) = fs::read_to_string(path) {
+        inputs.push(("real", real));
+    }
+
+    inputs
+}
+
+fn bench_day5(c: &mut Criterion) {
+    for (name, input) in inputs(5, day5::EXAMPLE) {
+        c.bench_function(&format!("day5/{}/parse", name), |b| {
+            b.iter(|| black_box(&input).parse::<day5::Almanac>().unwrap())
+        });
+
+        let parsed = input.parse::<day5::Almanac>().unwrap();
+        c.bench_function(&format!("day5/{}/task2", name), |b| {
+            b.iter(|| day5::task2(black_box(&parsed)).unwrap())
+        });
+    }
+}
+
+fn bench_day6(c: &mut Criterion) {
+    for (name, input) in inputs(6, day6::EXAMPLE) {
+        c.bench_function(&format!("day6/{}/parse", name), |b| {
+            b.iter(|| day6::Day6::parse(black_box(&input), Parts::Both).unwrap())
+        });
+
+        let record = day6::Day6::parse(&input, Parts::Two).unwrap().1.unwrap();
+        c.bench_function(&format!("day6/{}/task2", name), |b| {
+            b.iter(|| day6::task2(black_box(&record)).unwrap())
+        });
+    }
+}
+
+fn bench_day7(c: &mut Criterion) {
+    for (name, input) in inputs(7, day7::EXAMPLE) {
+        c.bench_function(&format!("day7/{}/parse", name), |b| {
+            b.iter(|| day7::Day7::parse(black_box(&input), Parts::Both).unwrap())
+        });
+
+        let (bids, bids_t2) = day7::Day7::parse(&input, Parts::Both).unwrap();
+        c.bench_function(&format!("day7/{}/task1", name), |b| {
+            b.iter(|| day7::total_winnings(black_box(bids.as_ref().unwrap())).unwrap())
+        });
+        c.bench_function(&format!("day7/{}/task2", name), |b| {
+            b.iter(|| day7::total_winnings(black_box(bids_t2.as_ref().unwrap())).unwrap())
+        });
+    }
+}
+
+fn bench_day8(c: &mut Criterion) {
+    for (name, input) in inputs(8, day8::EXAMPLE_TASK2) {
+        c.bench_function(&format!("day8/{}/parse", name), |b| {
+            b.iter(|| day8::Day8::parse(black_box(&input), Parts::Both).unwrap())
+        });
+
+        let (instructions, map) = day8::Day8::parse(&input, Parts::Both).unwrap();
+        c.bench_function(&format!("day8/{}/task2", name), |b| {
+            b.iter(|| day8::task2(black_box(&instructions), black_box(&map)).unwrap())
+        });
+    }
+}
+
+fn bench_day9(c: &mut Criterion) {
+    for (name, input) in inputs(9, day9::EXAMPLE) {
+        c.bench_function(&format!("day9/{}/parse", name), |b| {
+            b.iter(|| day9::Day9::parse(black_box(&input), Parts::Both).unwrap())
+        });
+
+        let sequences = day9::Day9::parse(&input, Parts::Both).unwrap();
+        c.bench_function(&format!("day9/{}/task1", name), |b| {
+            b.iter(|| day9::task1(black_box(&sequences)).unwrap())
+        });
+    }
+}
+
+criterion_group!(benches, bench_day5, bench_day6, bench_day7, bench_day8, bench_day9);
+criterion_main!(benches);
@@ -0,0 +1,187 @@
+use std::{collections::HashMap, fmt, fs, path::Path};
+
+use serde::Deserialize;
+
+use crate::prelude::*;
+
+/// Known correct answers, read from an `answers.toml` such as:
+///
+/// ```toml
+/// [5]
+/// part1 = 35
+/// part2 = "56"
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers(HashMap<u8, DayAnswers>);
+
+#[derive(Debug, Default, Deserialize)]
+struct DayAnswers {
+    part1: Option<Expected>,
+    part2: Option<Expected>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Expected {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Number(n) => write!(f, "{}", n),
+            Expected::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("could not read answers file {}", path.display()))?;
+
+        content
+            .parse()
+            .with_context(|| format!("invalid answers file {}", path.display()))
+    }
+
+    pub fn expected(&self, day: u8, part: u8) -> Option<String> {
+        let answers = self.0.get(&day)?;
+
+        match part {
+            1 => answers.part1.as_ref(),
+            2 => answers.part2.as_ref(),
+            _ => None,
+        }
+        .map(Expected::to_string)
+    }
+
+    pub fn verdict(&self, day: u8, part: u8, actual: &str) -> Verdict {
+        match self.expected(day, part) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == actual => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected,
+                actual: actual.to_string(),
+            },
+        }
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let days: HashMap<String, DayAnswers> = toml::from_str(s)?;
+
+        days.into_iter()
+            .map(|(day, answers)| {
+                let day = day
+                    .parse::<u8>()
+                    .with_context(|| format!("{:?} is not a day number", day))?;
+                Ok((day, answers))
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String, actual: String },
+    Unknown,
+}
+
+impl Verdict {
+    pub fn failed(&self) -> bool {
+        matches!(self, Verdict::Fail { .. })
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "PASS",
+            Verdict::Fail { .. } => "FAIL",
+            Verdict::Unknown => "UNKNOWN",
+        }
+    }
+
+    /// Expected and actual answers on top of each other, with a caret under the first difference.
+    pub fn diff(&self) -> Option<String> {
+        let Verdict::Fail { expected, actual } = self else {
+            return None;
+        };
+
+        let offset = expected
+            .chars()
+            .zip(actual.chars())
+            .take_while(|(e, a)| e == a)
+            .count();
+
+        Some(format!(
+            "expected: {}\n  actual: {}\n          {}^",
+            expected,
+            actual,
+            " ".repeat(offset)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"
+[5]
+part1 = 35
+part2 = "56"
+
+[6]
+part1 = 288
+"#;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = ANSWERS.parse::<Answers>().unwrap();
+
+        assert_eq!(answers.expected(5, 1), Some("35".to_string()));
+        assert_eq!(answers.expected(5, 2), Some("56".to_string()));
+        assert_eq!(answers.expected(6, 2), None);
+        assert_eq!(answers.expected(7, 1), None);
+    }
+
+    #[test]
+    fn test_invalid_day() {
+        assert!("[five]\npart1 = 1".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn test_verdict() {
+        let answers = ANSWERS.parse::<Answers>().unwrap();
+
+        assert_eq!(answers.verdict(5, 1, "35"), Verdict::Pass);
+        assert_eq!(answers.verdict(6, 2, "71503"), Verdict::Unknown);
+        assert_eq!(
+            answers.verdict(5, 2, "46"),
+            Verdict::Fail {
+                expected: "56".to_string(),
+                actual: "46".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let verdict = Verdict::Fail {
+            expected: "1234".to_string(),
+            actual: "1244".to_string(),
+        };
+
+        assert_eq!(
+            verdict.diff().unwrap(),
+            "expected: 1234\n  actual: 1244\n            ^"
+        );
+        assert_eq!(Verdict::Pass.diff(), None);
+    }
+}
@@ -1,61 +1,80 @@
-use std::str::FromStr;
+mod category;
+mod compose;
+mod export;
+mod inverse;
+mod trace;
+mod validate;
 
+use std::{fmt, str::FromStr};
+
+use clap::Subcommand;
 use itertools::Itertools;
+use rayon::prelude::*;
 
-use crate::prelude::*;
+use crate::{
+    interval::{Interval, IntervalSet},
+    prelude::*,
+    solution::Finding,
+};
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
-struct Range {
-    start: i128,
-    end: i128,
-}
+pub use category::{CategoryGraph, Mapping};
+pub use compose::{Piece, PiecewiseMap};
+pub use export::{stages, to_csv, to_json, ExportFormat, Fragment, Line, Stage};
+pub use inverse::{lowest_location_by_scan, seeds_for_locations};
+pub use trace::{trace, Move, Step, Trace};
+pub use validate::{validate, Issue};
 
-impl Range {
-    fn new(start: i128, end: i128) -> Self {
-        Range { start, end }
-    }
+pub struct Day5;
 
-    fn overlaps(&self, other: &Range) -> bool {
-        self.start < other.end && other.start < self.end
-    }
+impl Solution for Day5 {
+    const DAY: u8 = 5;
 
-    fn shift(&self, offset: i128) -> Range {
-        Range::new(self.start + offset, self.end + offset)
-    }
+    type Input = Almanac;
+    type Answer1 = i128;
+    type Answer2 = i128;
 
-    fn contains(&self, n: i128) -> bool {
-        self.start <= n && n <= self.end
+    fn parse(input: &str, _parts: Parts) -> Result<Self::Input> {
+        input.parse()
     }
-}
 
-pub fn run_day() -> Result<()> {
-    let input = include_str!("../input/5.txt");
+    fn part1(input: &Self::Input) -> Result<i128> {
+        task1(input)
+    }
 
-    println!("task 1: {}", task1(input)?);
-    println!("task 2: {}", task2(input)?);
+    fn part2(input: &Self::Input) -> Result<i128> {
+        task2(input)
+    }
 
-    Ok(())
+    fn validate(input: &Self::Input) -> Vec<Finding> {
+        validate(&input.maps)
+            .into_iter()
+            .map(|issue| Finding {
+                severity: issue.severity(),
+                message: issue.to_string(),
+            })
+            .collect()
+    }
 }
 
-fn task1(input: &str) -> Result<i128> {
-    let input = input.parse::<Task1Input>()?;
+const SEED: &str = "seed";
+const LOCATION: &str = "location";
 
-    let seeds = input.seeds;
-    let mappers = input.mappers;
+fn task1(input: &Almanac) -> Result<i128> {
+    let path = input.maps.path(SEED, LOCATION)?;
 
-    let minimum = seeds
-        .into_iter()
+    input
+        .seeds_as_points()
+        .par_iter()
+        .copied()
         .map(|mut n| {
-            for mapper in &mappers {
-                n = transform(n, mapper);
+            for mapping in &path {
+                n = transform(n, &mapping.transformations);
             }
 
             n
         })
         .min()
-        .unwrap();
-
-    Ok(minimum)
+        .context("there are no seeds to plant")
 }
 
 fn transform(n: i128, transformations: &[Transformation]) -> i128 {
@@ -66,34 +85,132 @@ fn transform(n: i128, transformations: &[Transformation]) -> i128 {
         .unwrap_or(n)
 }
 
-fn task2(input: &str) -> Result<i128> {
-    let input = input.parse::<Task2Input>()?;
+/// Every seed range is followed through the blocks on its own, spread over the rayon pool.
+pub fn task2(input: &Almanac) -> Result<i128> {
+    let path = input.maps.path(SEED, LOCATION)?;
+    let seeds: Vec<Interval> = input.seeds_as_ranges()?.into_iter().collect();
+
+    seeds
+        .into_par_iter()
+        .filter_map(|seeds| {
+            path.iter()
+                .fold(IntervalSet::from(seeds), |ranges, mapping| {
+                    apply_transformations(&ranges, &mapping.transformations)
+                })
+                .min()
+        })
+        .min()
+        .context("there are no seeds to plant")
+}
 
-    let seeds = input.seeds;
-    let mappers = input.mappers;
+/// Part 2 by walking every single seed through [`transform`], far too slow for the real input
+/// but simple enough to check [`task2`] against on small ones.
+pub fn task2_reference(input: &Almanac) -> Result<i128> {
+    let path = input.maps.path(SEED, LOCATION)?;
 
-    let mut result = Vec::new();
+    input
+        .seeds_as_ranges()?
+        .iter()
+        .flat_map(|range| range.start..range.end)
+        .map(|seed| {
+            path.iter()
+                .fold(seed, |n, mapping| transform(n, &mapping.transformations))
+        })
+        .min()
+        .context("there are no seeds to plant")
+}
 
-    for seed_range in seeds {
-        let mut ranges = vec![seed_range];
+/// Tools for inspecting the almanac, e.g. `aoc2023 day5 compose`.
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Print the single map composed from every block between two categories
+    Compose {
+        /// Category to convert from
+        #[arg(long, default_value = SEED)]
+        from: String,
+        /// Category to convert to
+        #[arg(long, default_value = LOCATION)]
+        to: String,
+    },
+    /// Print every seed that ends up at the given locations, working backwards from them
+    Seeds {
+        /// First location to look up
+        location: i128,
+        /// How many consecutive locations to look up
+        #[arg(default_value_t = 1)]
+        len: i128,
+    },
+    /// Print every category a seed or seed range passes through and which lines moved it
+    Trace {
+        /// First value to trace, a seed unless `--from` says otherwise
+        start: i128,
+        /// How many consecutive values to trace
+        #[arg(default_value_t = 1)]
+        len: i128,
+        /// Category to start from
+        #[arg(long, default_value = SEED)]
+        from: String,
+        /// Category to stop at
+        #[arg(long, default_value = LOCATION)]
+        to: String,
+    },
+    /// Print the lines of every block and the seed ranges after it, for plotting elsewhere
+    Export {
+        #[arg(short, long, value_enum, default_value_t)]
+        format: ExportFormat,
+    },
+}
 
-        for block in &mappers {
-            ranges = Vec::from_iter(
-                ranges
-                    .into_iter()
-                    .flat_map(|r| apply_transformations(r, block.clone())),
-            );
+pub fn run_command(command: &Command, input: &str) -> Result<()> {
+    let almanac = input.parse::<Almanac>()?;
+
+    match command {
+        Command::Compose { from, to } => {
+            let map = PiecewiseMap::from_blocks(&almanac.maps.path(from, to)?);
+            print!("{}", map);
+
+            if from == SEED {
+                if let Some(lowest) = map.apply_set(&almanac.seeds_as_ranges()?).min() {
+                    println!("lowest {} for the seed ranges: {}", to, lowest);
+                }
+            }
         }
+        Command::Seeds { location, len } => {
+            let locations = IntervalSet::from(Interval::with_len(*location, *len));
+            let path = almanac.maps.path(SEED, LOCATION)?;
+            let seeds = seeds_for_locations(&path, &locations);
+            let planted = almanac.seeds_as_ranges()?;
+
+            println!("seeds reaching {}: {}", locations, seeds);
+            println!("of which planted: {}", seeds.intersection(&planted));
+        }
+        Command::Trace {
+            start,
+            len,
+            from,
+            to,
+        } => {
+            let path = almanac.maps.path(from, to)?;
+            let start = IntervalSet::from(Interval::with_len(*start, *len));
+
+            print!("{}", trace(from, &path, start));
+        }
+        Command::Export { format } => {
+            let stages = stages(&almanac)?;
 
-        result.push(ranges.iter().min_by_key(|r| r.start).unwrap().start);
+            match format {
+                ExportFormat::Json => println!("{}", to_json(&stages)?),
+                ExportFormat::Csv => print!("{}", to_csv(&stages)),
+            }
+        }
     }
 
-    Ok(*result.iter().min().unwrap())
+    Ok(())
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
-struct Transformation {
-    range: Range,
+pub struct Transformation {
+    range: Interval,
     op: i128,
 }
 
@@ -103,252 +220,418 @@ impl Transformation {
     }
 }
 
+/// The line the transformation was read from.
+impl fmt::Display for Transformation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.range.start + self.op,
+            self.range.start,
+            self.range.len()
+        )
+    }
+}
+
 impl FromStr for Transformation {
-    type Err = Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self> {
-        let mut parts = s.split_ascii_whitespace();
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut tokens = Tokens::new(s);
 
-        let end = parts.next().map(|c| c.parse::<i128>()).unwrap()?;
-        let start = parts.next().map(|c| c.parse::<i128>()).unwrap()?;
-        let size = parts.next().map(|c| c.parse::<i128>()).unwrap()?;
+        let end = tokens.parse_next::<i128>("destination range start")?;
+        let start = tokens.parse_next::<i128>("source range start")?;
+        let size = tokens.parse_next::<u64>("range length")?;
+        tokens.finish()?;
 
         Ok(Self {
-            range: Range::new(start, start + size),
+            range: Interval::with_len(start, size.into()),
             op: end - start,
         })
     }
 }
 
+/// Line offset and column of a number in the almanac.
+type Position = (usize, usize);
+
+/// The seeds line and every map block. Part 1 reads the seeds as single numbers, part 2 as
+/// `start length` pairs.
 #[derive(Debug)]
-struct Task2Input {
-    seeds: Vec<Range>,
-    mappers: Vec<Vec<Transformation>>,
+pub struct Almanac {
+    seeds: Vec<i128>,
+    /// Where every seed number starts, for errors about the seed ranges.
+    seed_positions: Vec<Position>,
+    maps: CategoryGraph,
 }
 
-impl FromStr for Task2Input {
-    type Err = Error;
-
-    fn from_str(s: &str) -> std::prelude::v1::Result<Self, Self::Err> {
-        let mut chunks = s.split("\n\n");
-
-        let seeds = chunks
-            .next()
-            .and_then(|line| line.split_once(": "))
-            .map(|(_, nums)| {
-                let mut ranges = Vec::new();
-                for mut chunk in &nums
-                    .split_ascii_whitespace()
-                    .map(|n| n.parse::<i128>().unwrap())
-                    .chunks(2)
-                {
-                    ranges.push(Range::new(chunk.next().unwrap(), chunk.next().unwrap()))
-                }
+impl Almanac {
+    pub fn seeds_as_points(&self) -> &[i128] {
+        &self.seeds
+    }
 
-                ranges
-            })
-            .unwrap();
-
-        let mappers = chunks
-            .map(|chunk| {
-                chunk
-                    .lines()
-                    .skip(1)
-                    .map(|line| line.parse::<Transformation>().unwrap())
-                    .collect()
-            })
-            .collect();
+    /// The seeds as `start length` pairs, failing when the last start has no length or a
+    /// length is negative.
+    pub fn seeds_as_ranges(&self) -> Result<IntervalSet> {
+        if !self.seeds.len().is_multiple_of(2) {
+            bail!(
+                "seed ranges need pairs of start and length, but there are {} seed numbers",
+                self.seeds.len()
+            );
+        }
+        if let Some((len, (offset, column))) = self
+            .seeds
+            .iter()
+            .zip(&self.seed_positions)
+            .skip(1)
+            .step_by(2)
+            .find(|(len, _)| **len < 0)
+        {
+            return Err(
+                ParseError::new(*column, "seed range length", &len.to_string())
+                    .offset(*offset)
+                    .into(),
+            );
+        }
 
-        Ok(Self { seeds, mappers })
+        Ok(self
+            .seeds
+            .iter()
+            .tuples()
+            .map(|(&start, &len)| Interval::with_len(start, len))
+            .collect())
     }
-}
 
-#[derive(Debug)]
-struct Task1Input {
-    seeds: Vec<i128>,
-    mappers: Vec<Vec<Transformation>>,
+    pub fn maps(&self) -> &CategoryGraph {
+        &self.maps
+    }
 }
 
-impl FromStr for Task1Input {
+impl FromStr for Almanac {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        let mut chunks = s.split("\n\n");
-
-        let seeds = chunks
-            .next()
-            .and_then(|line| line.split_once(": "))
-            .map(|(_, nums)| {
-                nums.split_ascii_whitespace()
-                    .map(|n| n.parse::<i128>().unwrap())
-            })
-            .unwrap()
-            .collect();
-
-        let mappers = chunks
-            .map(|chunk| {
-                chunk
-                    .lines()
-                    .skip(1)
-                    .map(|line| line.parse::<Transformation>().unwrap())
-                    .collect()
-            })
-            .collect();
+        let (seeds, seed_positions) = parse_seeds(s)?.into_iter().unzip();
 
-        Ok(Self { seeds, mappers })
+        Ok(Self {
+            seeds,
+            seed_positions,
+            maps: parse_maps(s)?,
+        })
     }
 }
 
-fn apply_transformations(base: Range, transforms: Vec<Transformation>) -> Vec<Range> {
-    let mut result = Vec::new();
+/// The numbers on the `seeds: ...` line that opens the almanac, with where they start.
+fn parse_seeds(s: &str) -> Result<Vec<(i128, Position)>, ParseError> {
+    let (offset, block) = blocks(s)
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(1, "seeds line"))?;
+    let line = block.lines().next().unwrap_or_default();
+
+    let mut tokens = Tokens::new(line);
+    let label = tokens.expect("\"seeds:\"").map_err(|e| e.offset(offset))?;
+    if label.text != "seeds:" {
+        return Err(label.error("\"seeds:\"").offset(offset));
+    }
 
-    for transformation in transforms.clone() {
-        let mask = transformation.range;
-        let offset = transformation.op;
+    tokens
+        .map(|token| Ok((token.parse::<i128>("seed number")?, (offset, token.column))))
+        .collect::<Result<_, ParseError>>()
+        .map_err(|e| e.offset(offset))
+}
 
-        // 1 - no overlap; skip this mask
-        if !base.overlaps(&mask) {
-            continue;
-        }
+/// Every `x-to-y map:` block after the seeds, named by the categories in its header.
+fn parse_maps(s: &str) -> Result<CategoryGraph, ParseError> {
+    let mappings = blocks(s)
+        .skip(1)
+        .map(|(offset, block)| {
+            let mut lines = block.lines();
+
+            let header = lines.next().unwrap_or_default();
+            let (source, destination) = header
+                .strip_suffix(" map:")
+                .and_then(|names| names.split_once("-to-"))
+                .filter(|(source, destination)| !source.is_empty() && !destination.is_empty())
+                .ok_or_else(|| {
+                    ParseError::new(1, "map header like \"seed-to-soil map:\"", header)
+                        .offset(offset)
+                })?;
+
+            let transformations = lines
+                .enumerate()
+                .map(|(idx, line)| {
+                    line.parse::<Transformation>()
+                        .map_err(|e| e.offset(offset + idx + 1))
+                })
+                .collect::<Result<_, _>>()?;
+
+            Ok(Mapping {
+                source: source.to_string(),
+                destination: destination.to_string(),
+                transformations,
+            })
+        })
+        .collect::<Result<_, _>>()?;
 
-        // 2 - base is inside mask, shift entire base
-        if mask.start <= base.start && base.end <= mask.end {
-            return vec![base.shift(offset)];
-        }
+    Ok(CategoryGraph::new(mappings))
+}
 
-        // 3 - mask is a subset of base
-        // return unshifted left, shifted middle, and recurse for the rest
-        if base.start <= mask.start && mask.end <= base.end {
-            result.push(Range {
-                start: base.start,
-                end: mask.start,
-            });
-            result.push(mask.shift(offset));
-            result.extend(apply_transformations(
-                Range {
-                    start: mask.end,
-                    end: base.end,
-                },
-                transforms.clone(),
-            ));
-            return result;
-        }
+/// Maps every number in `ranges` through one block, the same split a trace records.
+fn apply_transformations(ranges: &IntervalSet, transforms: &[Transformation]) -> IntervalSet {
+    trace::moves(ranges, transforms)
+        .into_iter()
+        .map(|m| m.to)
+        .collect()
+}
 
-        // 4 - mask overlaps only the left side,
-        // return masked left, recurse for the rest
-        if mask.start <= base.start && mask.end <= base.end {
-            result.push(Range {
-                start: base.start + offset,
-                end: mask.end + offset,
-            });
-            result.extend(apply_transformations(
-                Range {
-                    start: mask.end,
-                    end: base.end,
-                },
-                transforms.clone(),
-            ));
-            return result;
-        }
+/// The example almanac from the puzzle description.
+pub const EXAMPLE: &str = r#"seeds: 79 14 55 13
 
-        // 5 - mask overlaps only the right side
-        // return unshifted left, masked right
-        if base.start <= mask.start && base.end <= mask.end {
-            result.push(Range {
-                start: base.start,
-                end: mask.start,
-            });
-            result.push(Range {
-                start: mask.start + offset,
-                end: base.end + offset,
-            });
-            return result;
-        }
-    }
+seed-to-soil map:
+50 98 2
+52 50 48
 
-    // no masks overlapped this base; pass it through
-    result.push(base);
-    result
-}
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4"#;
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
 
     use super::*;
 
+    /// Almanac text with up to five blocks of possibly overlapping lines, and small seed ranges.
+    fn arb_almanac() -> impl Strategy<Value = String> {
+        let line = (0i128..60, 0i128..60, 0i128..20);
+        let block = prop::collection::vec(line, 0..5);
+        let seeds = prop::collection::vec((0i128..60, 0i128..15), 0..4);
+
+        (seeds, prop::collection::vec(block, 1..6)).prop_map(|(seeds, blocks)| {
+            let mut text = format!(
+                "seeds: {}",
+                seeds
+                    .iter()
+                    .map(|(start, len)| format!("{} {}", start, len))
+                    .join(" ")
+            );
+
+            let mut names = vec![SEED.to_string()];
+            names.extend((1..blocks.len()).map(|idx| format!("stage{}", idx)));
+            names.push(LOCATION.to_string());
+
+            for (idx, block) in blocks.iter().enumerate() {
+                text += &format!("\n\n{}-to-{} map:", names[idx], names[idx + 1]);
+                for (destination, source, len) in block {
+                    text += &format!("\n{} {} {}", destination, source, len);
+                }
+            }
+
+            text
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_task2_matches_reference(text in arb_almanac()) {
+            let almanac = text.parse::<Almanac>().unwrap();
+
+            prop_assert_eq!(
+                task2(&almanac).ok(),
+                task2_reference(&almanac).ok()
+            );
+        }
+    }
+
     #[test]
-    fn parse_task2() {
-        let parsed = TEST.parse::<Task2Input>().unwrap();
+    fn seeds_as_ranges() {
+        let parsed = EXAMPLE.parse::<Almanac>().unwrap();
+
+        assert_eq!(
+            parsed.seeds_as_ranges().unwrap(),
+            [Interval::new(79, 93), Interval::new(55, 68)]
+                .into_iter()
+                .collect()
+        );
+
+        let odd = EXAMPLE.replace("seeds: 79 14 55 13", "seeds: 79 14 55");
+        let err = odd
+            .parse::<Almanac>()
+            .unwrap()
+            .seeds_as_ranges()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "seed ranges need pairs of start and length, but there are 3 seed numbers"
+        );
 
-        assert_eq!(parsed.seeds.len(), 2);
-        assert_eq!(parsed.mappers.len(), 7);
+        let negative = EXAMPLE.replace("seeds: 79 14 55 13", "seeds: 79 14 55 -13");
+        let err = negative
+            .parse::<Almanac>()
+            .unwrap()
+            .seeds_as_ranges()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"1:17: expected seed range length, found "-13""#
+        );
     }
 
     #[test]
     fn test_task1() {
-        let result = task1(TEST).unwrap();
+        let result = task1(&EXAMPLE.parse().unwrap()).unwrap();
 
         assert_eq!(result, 35);
     }
 
     #[test]
     fn test_task2() {
-        let result = task2(TEST).unwrap();
+        let result = task2(&EXAMPLE.parse().unwrap()).unwrap();
+
+        assert_eq!(result, 46)
+    }
 
-        // For some reason, the result for the example input is wrong, but it works for the actual??
-        assert_eq!(result, 56)
+    #[test]
+    fn test_task2_reference() {
+        let result = task2_reference(&EXAMPLE.parse().unwrap()).unwrap();
+
+        assert_eq!(result, 46)
+    }
+
+    #[test]
+    fn test_thread_count() {
+        let almanac = include_str!("../input/5.txt").parse::<Almanac>().unwrap();
+
+        for threads in [1, 4] {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+
+            assert_eq!(pool.install(|| task1(&almanac)).unwrap(), 340994526);
+            assert_eq!(pool.install(|| task2(&almanac)).unwrap(), 52210644);
+        }
     }
 
     #[test]
     fn parse_input() {
-        let parsed = TEST.parse::<Task1Input>().unwrap();
+        let parsed = EXAMPLE.parse::<Almanac>().unwrap();
 
-        assert_eq!(parsed.seeds.len(), 4);
-        assert_eq!(parsed.mappers.len(), 7);
+        assert_eq!(parsed.seeds_as_points(), [79, 14, 55, 13]);
+        assert_eq!(parsed.maps.mappings().len(), 7);
+        assert_eq!(parsed.maps.mappings()[0].source, "seed");
+        assert_eq!(parsed.maps.mappings()[0].destination, "soil");
     }
 
     #[test]
-    fn parse_transformation() {
-        assert_eq!(
-            "50 98 2".parse::<Transformation>().unwrap(),
-            Transformation {
-                op: -48,
-                range: Range::new(98, 100)
-            }
-        )
+    fn test_blocks_out_of_order() {
+        let (seeds, maps) = EXAMPLE.split_once("\n\n").unwrap();
+        let shuffled = format!(
+            "{}\n\n{}",
+            seeds,
+            maps.rsplit("\n\n").collect::<Vec<_>>().join("\n\n")
+        );
+
+        assert_eq!(task1(&shuffled.parse().unwrap()).unwrap(), 35);
+        assert_eq!(task2(&shuffled.parse().unwrap()).unwrap(), 46);
     }
 
-    const TEST: &str = r#"seeds: 79 14 55 13
+    #[test]
+    fn parse_errors() {
+        let err = "50 98 x".parse::<Transformation>().unwrap_err();
+        assert_eq!(err.to_string(), r#"1:7: expected range length, found "x""#);
+        let err = "0 10 -5".parse::<Transformation>().unwrap_err();
+        assert_eq!(err.to_string(), r#"1:6: expected range length, found "-5""#);
+
+        let input = EXAMPLE.replace("52 50 48", "52 50");
+        let err = input.parse::<Almanac>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "5:6: expected range length, found end of line"
+        );
 
-seed-to-soil map:
-50 98 2
-52 50 48
+        let err = "seed: 1 2".parse::<Almanac>().unwrap_err();
+        assert_eq!(err.to_string(), r#"1:1: expected "seeds:", found "seed:""#);
 
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
+        let input = EXAMPLE.replace("seed-to-soil map:", "seed-soil map:");
+        let err = input.parse::<Almanac>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"3:1: expected map header like "seed-to-soil map:", found "seed-soil map:""#
+        );
+    }
 
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
+    #[test]
+    fn test_apply_transformations() {
+        let block = vec![
+            "50 98 2".parse::<Transformation>().unwrap(),
+            "52 50 48".parse::<Transformation>().unwrap(),
+        ];
+        let seeds = IntervalSet::from(Interval::new(45, 100));
 
-water-to-light map:
-88 18 7
-18 25 70
+        assert_eq!(
+            apply_transformations(&seeds, &block),
+            [
+                Interval::new(45, 50),
+                Interval::new(50, 52),
+                Interval::new(52, 100)
+            ]
+            .into_iter()
+            .collect()
+        );
+
+        // 97 and 99 sit right at the end of both source ranges
+        let edges = IntervalSet::from(Interval::new(97, 101));
+        assert_eq!(
+            apply_transformations(&edges, &block),
+            [
+                Interval::new(99, 100),
+                Interval::new(50, 52),
+                Interval::new(100, 101)
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
 
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
+    #[test]
+    fn display_transformation() {
+        let line = "52 50 48";
 
-temperature-to-humidity map:
-0 69 1
-1 0 69
+        assert_eq!(line.parse::<Transformation>().unwrap().to_string(), line);
+    }
 
-humidity-to-location map:
-60 56 37
-56 93 4"#;
+    #[test]
+    fn parse_transformation() {
+        assert_eq!(
+            "50 98 2".parse::<Transformation>().unwrap(),
+            Transformation {
+                op: -48,
+                range: Interval::new(98, 100)
+            }
+        )
+    }
 }
@@ -0,0 +1,140 @@
+use itertools::Itertools;
+
+use super::Transformation;
+use crate::prelude::*;
+
+/// One `source-to-destination map:` block of the almanac.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping {
+    pub source: String,
+    pub destination: String,
+    pub transformations: Vec<Transformation>,
+}
+
+/// Every block of the almanac as an edge between two categories, so conversions are found by
+/// following the names rather than the order the blocks were written in.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CategoryGraph {
+    mappings: Vec<Mapping>,
+}
+
+impl CategoryGraph {
+    pub fn new(mappings: Vec<Mapping>) -> Self {
+        Self { mappings }
+    }
+
+    /// The blocks in input order.
+    pub fn mappings(&self) -> &[Mapping] {
+        &self.mappings
+    }
+
+    /// The blocks converting `from` into `to`, in the order they apply. Fails when no chain of
+    /// blocks connects the two categories, or when more than one does.
+    pub fn path(&self, from: &str, to: &str) -> Result<Vec<&Mapping>> {
+        let mut found = Vec::new();
+        self.walk(from, to, &mut Vec::new(), &mut found);
+
+        match found.as_slice() {
+            [] => bail!("no chain of maps leads from {} to {}", from, to),
+            [path] => Ok(path.clone()),
+            [first, second, ..] => bail!(
+                "more than one chain of maps leads from {} to {}: {} and {}",
+                from,
+                to,
+                describe(from, first),
+                describe(from, second)
+            ),
+        }
+    }
+
+    /// Depth first search over every simple path, stopping once a second one shows up.
+    fn walk<'a>(
+        &'a self,
+        at: &str,
+        to: &str,
+        path: &mut Vec<&'a Mapping>,
+        found: &mut Vec<Vec<&'a Mapping>>,
+    ) {
+        if at == to {
+            found.push(path.clone());
+            return;
+        }
+
+        for mapping in self.mappings.iter().filter(|m| m.source == at) {
+            let visited = mapping.destination == at
+                || path.iter().any(|step| step.source == mapping.destination);
+            if visited || found.len() > 1 {
+                continue;
+            }
+
+            path.push(mapping);
+            self.walk(&mapping.destination, to, path, found);
+            path.pop();
+        }
+    }
+}
+
+/// `seed -> soil -> fertilizer`
+fn describe(from: &str, path: &[&Mapping]) -> String {
+    std::iter::once(from)
+        .chain(path.iter().map(|mapping| mapping.destination.as_str()))
+        .join(" -> ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day5::{Almanac, EXAMPLE};
+
+    fn names(path: &[&Mapping]) -> Vec<String> {
+        path.iter()
+            .map(|m| format!("{}-{}", m.source, m.destination))
+            .collect()
+    }
+
+    #[test]
+    fn test_path() {
+        let graph = EXAMPLE.parse::<Almanac>().unwrap().maps;
+
+        assert_eq!(graph.path("seed", "location").unwrap().len(), 7);
+        assert_eq!(
+            names(&graph.path("soil", "humidity").unwrap()),
+            [
+                "soil-fertilizer",
+                "fertilizer-water",
+                "water-light",
+                "light-temperature",
+                "temperature-humidity"
+            ]
+        );
+        assert!(graph.path("water", "water").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_broken_chain() {
+        let graph = EXAMPLE.parse::<Almanac>().unwrap().maps;
+
+        let err = graph.path("location", "seed").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "no chain of maps leads from location to seed"
+        );
+
+        let input = EXAMPLE.replace("water-to-light", "water-to-sunlight");
+        let graph = input.parse::<Almanac>().unwrap().maps;
+        assert!(graph.path("seed", "location").is_err());
+    }
+
+    #[test]
+    fn test_ambiguous_chain() {
+        let input = format!("{}\n\nseed-to-water map:\n0 0 1", EXAMPLE);
+        let graph = input.parse::<Almanac>().unwrap().maps;
+
+        let err = graph.path("seed", "water").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "more than one chain of maps leads from seed to water: \
+             seed -> soil -> fertilizer -> water and seed -> water"
+        );
+    }
+}
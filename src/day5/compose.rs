@@ -0,0 +1,300 @@
+use std::fmt;
+
+use super::{Mapping, Transformation};
+use crate::interval::{Interval, IntervalSet};
+
+/// Every number in `range` is moved by `offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Piece {
+    pub range: Interval,
+    pub offset: i128,
+}
+
+/// A function on integers that adds a constant offset on each of its pieces and is the
+/// identity everywhere else.
+///
+/// Pieces are sorted and disjoint, never have an offset of 0, and touching pieces always have
+/// different offsets, so equal functions have equal pieces.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    pieces: Vec<Piece>,
+}
+
+impl PiecewiseMap {
+    pub fn identity() -> Self {
+        Self::default()
+    }
+
+    /// A single almanac block, where the first transformation covering a number wins.
+    pub fn from_block(block: &[Transformation]) -> Self {
+        let mut covered = IntervalSet::new();
+        let mut pieces = Vec::new();
+
+        for transformation in block {
+            let fresh = IntervalSet::from(transformation.range).difference(&covered);
+            pieces.extend(fresh.iter().map(|range| Piece {
+                range: *range,
+                offset: transformation.op,
+            }));
+            covered.insert(transformation.range);
+        }
+
+        Self::normalized(pieces)
+    }
+
+    /// Every block applied in order, e.g. seed → location for a whole almanac.
+    pub fn from_blocks(blocks: &[&Mapping]) -> Self {
+        blocks
+            .iter()
+            .map(|mapping| Self::from_block(&mapping.transformations))
+            .fold(Self::identity(), |map, next| map.then(&next))
+    }
+
+    pub fn pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+
+    /// The function that applies `self` first and `next` to its result.
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let Some(bounds) = self.hull(next) else {
+            return Self::identity();
+        };
+
+        let pieces = self
+            .segments(bounds)
+            .into_iter()
+            .flat_map(|first| {
+                next.segments(first.range.shift(first.offset))
+                    .into_iter()
+                    .map(move |second| Piece {
+                        range: second.range.shift(-first.offset),
+                        offset: first.offset + second.offset,
+                    })
+            })
+            .collect();
+
+        Self::normalized(pieces)
+    }
+
+    pub fn apply(&self, n: i128) -> i128 {
+        let idx = self.pieces.partition_point(|piece| piece.range.end <= n);
+
+        match self.pieces.get(idx) {
+            Some(piece) if piece.range.contains(n) => n + piece.offset,
+            _ => n,
+        }
+    }
+
+    /// The image of every number in `set`.
+    pub fn apply_set(&self, set: &IntervalSet) -> IntervalSet {
+        set.iter()
+            .flat_map(|interval| self.segments(*interval))
+            .map(|segment| segment.range.shift(segment.offset))
+            .collect()
+    }
+
+    /// Every number that the map sends into `set`.
+    pub fn preimage(&self, set: &IntervalSet) -> IntervalSet {
+        let moved: IntervalSet = self.pieces.iter().map(|piece| piece.range).collect();
+
+        let shifted = self.pieces.iter().flat_map(|piece| {
+            set.shift(-piece.offset)
+                .intersection(&IntervalSet::from(piece.range))
+        });
+
+        set.difference(&moved).into_iter().chain(shifted).collect()
+    }
+
+    /// Splits `bounds` at every piece boundary, including the identity stretches as offset 0.
+    fn segments(&self, bounds: Interval) -> Vec<Piece> {
+        let mut segments = Vec::new();
+        let mut cursor = bounds.start;
+
+        let first = self
+            .pieces
+            .partition_point(|piece| piece.range.end <= bounds.start);
+
+        for piece in &self.pieces[first..] {
+            if piece.range.start >= bounds.end {
+                break;
+            }
+
+            if piece.range.start > cursor {
+                segments.push(Piece {
+                    range: Interval::new(cursor, piece.range.start),
+                    offset: 0,
+                });
+            }
+
+            let overlap = piece
+                .range
+                .intersection(&bounds)
+                .expect("piece overlaps bounds");
+            segments.push(Piece {
+                range: overlap,
+                offset: piece.offset,
+            });
+            cursor = overlap.end;
+        }
+
+        if cursor < bounds.end {
+            segments.push(Piece {
+                range: Interval::new(cursor, bounds.end),
+                offset: 0,
+            });
+        }
+
+        segments
+    }
+
+    /// Smallest interval outside of which both maps are the identity.
+    fn hull(&self, other: &PiecewiseMap) -> Option<Interval> {
+        let ranges = self.pieces.iter().chain(&other.pieces).map(|p| p.range);
+
+        let start = ranges.clone().map(|range| range.start).min()?;
+        let end = ranges.map(|range| range.end).max()?;
+
+        Some(Interval::new(start, end))
+    }
+
+    fn normalized(mut pieces: Vec<Piece>) -> Self {
+        pieces.retain(|piece| piece.offset != 0 && !piece.range.is_empty());
+        pieces.sort_by_key(|piece| piece.range.start);
+
+        let mut merged: Vec<Piece> = Vec::with_capacity(pieces.len());
+        for piece in pieces {
+            match merged.last_mut() {
+                Some(last)
+                    if last.range.end == piece.range.start && last.offset == piece.offset =>
+                {
+                    last.range.end = piece.range.end
+                }
+                _ => merged.push(piece),
+            }
+        }
+
+        Self { pieces: merged }
+    }
+}
+
+impl fmt::Display for PiecewiseMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pieces.is_empty() {
+            return writeln!(f, "identity");
+        }
+
+        for piece in &self.pieces {
+            writeln!(
+                f,
+                "{} -> {} ({:+})",
+                piece.range,
+                piece.range.shift(piece.offset),
+                piece.offset
+            )?;
+        }
+
+        writeln!(f, "everything else maps to itself")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day5::{transform, Almanac, EXAMPLE};
+
+    #[test]
+    fn test_from_block() {
+        let block = vec![
+            "50 98 2".parse::<Transformation>().unwrap(),
+            "52 50 48".parse::<Transformation>().unwrap(),
+            // shadowed by the first two, only [100, 105) is left
+            "0 95 10".parse::<Transformation>().unwrap(),
+        ];
+
+        assert_eq!(
+            PiecewiseMap::from_block(&block).pieces(),
+            &[
+                Piece {
+                    range: Interval::new(50, 98),
+                    offset: 2
+                },
+                Piece {
+                    range: Interval::new(98, 100),
+                    offset: -48
+                },
+                Piece {
+                    range: Interval::new(100, 105),
+                    offset: -95
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matches_block_by_block() {
+        let input = EXAMPLE.parse::<Almanac>().unwrap();
+        let path = input.maps.path("seed", "location").unwrap();
+        let map = PiecewiseMap::from_blocks(&path);
+
+        for seed in -10..200 {
+            let expected = path
+                .iter()
+                .fold(seed, |n, mapping| transform(n, &mapping.transformations));
+
+            assert_eq!(map.apply(seed), expected, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_apply_set() {
+        let input = EXAMPLE.parse::<Almanac>().unwrap();
+        let map = PiecewiseMap::from_blocks(&input.maps.path("seed", "location").unwrap());
+
+        let seeds = input.seeds_as_ranges().unwrap();
+        assert_eq!(map.apply_set(&seeds).min(), Some(46));
+    }
+
+    #[test]
+    fn test_then() {
+        let first = PiecewiseMap::from_block(&["20 10 5".parse().unwrap()]);
+        let second = PiecewiseMap::from_block(&["10 20 5".parse().unwrap()]);
+
+        // moving [10, 15) up by 10 and back down leaves [20, 25) moved down on its own
+        assert_eq!(
+            first.then(&second).pieces(),
+            &[Piece {
+                range: Interval::new(20, 25),
+                offset: -10
+            }]
+        );
+        assert_eq!(first.then(&PiecewiseMap::identity()), first);
+    }
+
+    #[test]
+    fn test_preimage() {
+        let map =
+            PiecewiseMap::from_block(&["50 98 2".parse().unwrap(), "52 50 48".parse().unwrap()]);
+
+        // 50 and 51 are reached from 98 and 99, while 50 and 51 themselves move away
+        assert_eq!(
+            map.preimage(&IntervalSet::from(Interval::new(49, 53))),
+            [
+                Interval::new(49, 50),
+                Interval::new(50, 51),
+                Interval::new(98, 100)
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let map = PiecewiseMap::from_block(&["50 98 2".parse().unwrap()]);
+
+        assert_eq!(
+            map.to_string(),
+            "[98, 100) -> [50, 52) (-48)\neverything else maps to itself\n"
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+use super::{trace, Almanac, LOCATION, SEED};
+use crate::{
+    interval::{Interval, IntervalSet},
+    prelude::*,
+};
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// An array with one object per stage
+    #[default]
+    Json,
+    /// One row per transformation and per range, tagged with its stage
+    Csv,
+}
+
+/// A line of a block, spelled out for plotting.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    pub destination_start: i128,
+    pub source_start: i128,
+    pub length: i128,
+    pub offset: i128,
+}
+
+/// A range of a stage, tagged with the seed range it came from.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fragment {
+    /// Index of the seed range, in the order of the seeds stage.
+    pub seed_range: usize,
+    #[serde(flatten)]
+    pub range: Interval,
+}
+
+/// The seed ranges after passing through every block up to `category`.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Stage {
+    pub category: String,
+    /// The block that led to this stage, empty for the seeds themselves.
+    pub transformations: Vec<Line>,
+    /// Every seed range is followed on its own, so fragments of different ones never merge.
+    pub ranges: Vec<Fragment>,
+}
+
+/// The seeds and then one stage per block from seed to location, as part 2 sees them.
+pub fn stages(almanac: &Almanac) -> Result<Vec<Stage>> {
+    let path = almanac.maps.path(SEED, LOCATION)?;
+    let traces: Vec<_> = almanac
+        .seeds_as_ranges()?
+        .iter()
+        .map(|seeds| trace(SEED, &path, IntervalSet::from(*seeds)))
+        .collect();
+
+    // the ranges of every seed range at one stage, 0 being the seeds themselves
+    let fragments = |stage: usize| {
+        traces
+            .iter()
+            .enumerate()
+            .flat_map(|(seed_range, trace)| {
+                let ranges = match stage {
+                    0 => &trace.start,
+                    _ => &trace.steps[stage - 1].result,
+                };
+                ranges.iter().map(move |range| Fragment {
+                    seed_range,
+                    range: *range,
+                })
+            })
+            .collect()
+    };
+
+    let seeds = Stage {
+        category: SEED.to_string(),
+        transformations: Vec::new(),
+        ranges: fragments(0),
+    };
+
+    let steps = path.iter().enumerate().map(|(idx, mapping)| Stage {
+        category: mapping.destination.clone(),
+        transformations: mapping
+            .transformations
+            .iter()
+            .map(|t| Line {
+                destination_start: t.range.start + t.op,
+                source_start: t.range.start,
+                length: t.range.len(),
+                offset: t.op,
+            })
+            .collect(),
+        ranges: fragments(idx + 1),
+    });
+
+    Ok(std::iter::once(seeds).chain(steps).collect())
+}
+
+pub fn to_json(stages: &[Stage]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(stages)?)
+}
+
+/// Transformations are given by their source range and offset and leave the seed range empty,
+/// ranges leave the offset empty.
+pub fn to_csv(stages: &[Stage]) -> String {
+    let mut csv = String::from("stage,category,kind,seed_range,start,end,offset\n");
+
+    for (idx, stage) in stages.iter().enumerate() {
+        let category = csv_field(&stage.category);
+
+        for line in &stage.transformations {
+            csv += &format!(
+                "{},{},transformation,,{},{},{}\n",
+                idx,
+                category,
+                line.source_start,
+                line.source_start + line.length,
+                line.offset
+            );
+        }
+
+        for fragment in &stage.ranges {
+            csv += &format!(
+                "{},{},range,{},{},{},\n",
+                idx, category, fragment.seed_range, fragment.range.start, fragment.range.end
+            );
+        }
+    }
+
+    csv
+}
+
+/// Quotes fields that would otherwise split the row.
+fn csv_field(text: &str) -> String {
+    match text.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", text.replace('"', "\"\"")),
+        false => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day5::{apply_transformations, EXAMPLE};
+
+    #[test]
+    fn test_stages() {
+        let stages = stages(&EXAMPLE.parse().unwrap()).unwrap();
+
+        assert_eq!(stages.len(), 8);
+        assert_eq!(stages[0].category, "seed");
+        assert_eq!(stages[1].transformations.len(), 2);
+        assert_eq!(stages[7].category, "location");
+        assert_eq!(
+            stages[7].ranges.iter().map(|f| f.range.start).min(),
+            Some(46)
+        );
+    }
+
+    #[test]
+    fn test_stages_follow_task2() {
+        let almanac: Almanac = EXAMPLE.parse().unwrap();
+        let stages = stages(&almanac).unwrap();
+        let path = almanac.maps.path(SEED, LOCATION).unwrap();
+
+        for (seed_range, seeds) in almanac.seeds_as_ranges().unwrap().iter().enumerate() {
+            let mut ranges = IntervalSet::from(*seeds);
+
+            for (idx, stage) in stages.iter().enumerate() {
+                if idx > 0 {
+                    ranges = apply_transformations(&ranges, &path[idx - 1].transformations);
+                }
+
+                let exported: Vec<Interval> = stage
+                    .ranges
+                    .iter()
+                    .filter(|f| f.seed_range == seed_range)
+                    .map(|f| f.range)
+                    .collect();
+                assert_eq!(exported, ranges.iter().copied().collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_csv() {
+        let stages = stages(&EXAMPLE.parse().unwrap()).unwrap();
+        let csv = to_csv(&stages);
+
+        assert_eq!(
+            csv.lines().take(5).collect::<Vec<_>>(),
+            [
+                "stage,category,kind,seed_range,start,end,offset",
+                "0,seed,range,0,55,68,",
+                "0,seed,range,1,79,93,",
+                "1,soil,transformation,,98,100,-48",
+                "1,soil,transformation,,50,98,2",
+            ]
+        );
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+    }
+
+    #[test]
+    fn test_json() {
+        let stages = stages(&EXAMPLE.parse().unwrap()).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&to_json(&stages).unwrap()).unwrap();
+
+        assert_eq!(json[1]["transformations"][0]["destination_start"], 50);
+        assert_eq!(json[1]["ranges"][0]["seed_range"], 0);
+        assert_eq!(json[1]["ranges"][0]["start"], 57);
+    }
+}
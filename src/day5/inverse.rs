@@ -0,0 +1,107 @@
+use super::{Almanac, Mapping, PiecewiseMap, LOCATION, SEED};
+use crate::{
+    interval::{Interval, IntervalSet},
+    prelude::*,
+};
+
+/// Every seed that ends up at one of `locations`, found by undoing the blocks from the last one.
+pub fn seeds_for_locations(blocks: &[&Mapping], locations: &IntervalSet) -> IntervalSet {
+    blocks.iter().rev().fold(locations.clone(), |set, mapping| {
+        PiecewiseMap::from_block(&mapping.transformations).preimage(&set)
+    })
+}
+
+/// Part 2 answered backwards: scans location windows upwards until one of them is reachable
+/// from a planted seed, then narrows that window down to the lowest location.
+pub fn lowest_location_by_scan(input: &Almanac) -> Result<i128> {
+    let path = input.maps.path(SEED, LOCATION)?;
+    let planted = input.seeds_as_ranges()?;
+    let maps: Vec<PiecewiseMap> = path
+        .iter()
+        .map(|mapping| PiecewiseMap::from_block(&mapping.transformations))
+        .collect();
+
+    let reachable = |locations: Interval| {
+        let seeds = maps
+            .iter()
+            .rev()
+            .fold(IntervalSet::from(locations), |set, map| map.preimage(&set));
+        !seeds.intersection(&planted).is_empty()
+    };
+
+    // nothing can be moved past the planted seeds or the furthest destination range
+    let (Some(min), Some(max)) = (planted.min(), planted.max()) else {
+        bail!("there are no seeds to plant");
+    };
+    let (lowest, highest) = path
+        .iter()
+        .flat_map(|mapping| &mapping.transformations)
+        .map(|t| t.range.shift(t.op))
+        .chain(planted.iter().copied())
+        .fold((min, max + 1), |(lo, hi), range| {
+            (lo.min(range.start), hi.max(range.end))
+        });
+
+    let mut start = lowest;
+    let mut width = 1;
+    let mut window = loop {
+        if start >= highest {
+            bail!("no location is reachable from the planted seeds");
+        }
+
+        let window = Interval::new(start, (start + width).min(highest));
+        if reachable(window) {
+            break window;
+        }
+
+        start = window.end;
+        width *= 2;
+    };
+
+    while window.len() > 1 {
+        let mid = window.start + window.len() / 2;
+        let lower = Interval::new(window.start, mid);
+
+        window = match reachable(lower) {
+            true => lower,
+            false => Interval::new(mid, window.end),
+        };
+    }
+
+    Ok(window.start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day5::{task2, EXAMPLE};
+
+    #[test]
+    fn test_seeds_for_locations() {
+        let input = EXAMPLE.parse::<Almanac>().unwrap();
+        let path = input.maps.path(SEED, LOCATION).unwrap();
+
+        // the example walks seed 82 to location 46
+        let seeds = seeds_for_locations(&path, &IntervalSet::from(Interval::new(46, 47)));
+        assert!(seeds.contains(82));
+
+        let forward = PiecewiseMap::from_blocks(&path);
+        for interval in &seeds {
+            for seed in interval.start..interval.end {
+                assert_eq!(forward.apply(seed), 46);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scan_matches_range_splitting() {
+        for input in [EXAMPLE, include_str!("../../input/5.txt")] {
+            let input = input.parse::<Almanac>().unwrap();
+
+            assert_eq!(
+                lowest_location_by_scan(&input).unwrap(),
+                task2(&input).unwrap()
+            );
+        }
+    }
+}
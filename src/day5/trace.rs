@@ -0,0 +1,180 @@
+use std::fmt;
+
+use super::{Mapping, Transformation};
+use crate::interval::{Interval, IntervalSet};
+
+/// Where part of the ranges entering a block ended up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move<'a> {
+    pub from: Interval,
+    pub to: Interval,
+    /// The transformation that matched, `None` when the numbers passed through unchanged.
+    pub transformation: Option<&'a Transformation>,
+}
+
+/// One block of the path and what it did to the ranges entering it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step<'a> {
+    pub mapping: &'a Mapping,
+    /// Sorted by the start of the range they move.
+    pub moves: Vec<Move<'a>>,
+    pub result: IntervalSet,
+}
+
+/// The path of a set of numbers through a chain of blocks, recording every split.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace<'a> {
+    pub category: &'a str,
+    pub start: IntervalSet,
+    pub steps: Vec<Step<'a>>,
+}
+
+impl Trace<'_> {
+    /// The numbers that come out of the last block.
+    pub fn result(&self) -> &IntervalSet {
+        self.steps.last().map_or(&self.start, |step| &step.result)
+    }
+}
+
+/// Follows `start`, which belongs to the `from` category, through every block of `path`.
+pub fn trace<'a>(from: &'a str, path: &[&'a Mapping], start: IntervalSet) -> Trace<'a> {
+    let mut current = start.clone();
+
+    let steps = path
+        .iter()
+        .map(|mapping| {
+            let step = step(mapping, &current);
+            current = step.result.clone();
+            step
+        })
+        .collect();
+
+    Trace {
+        category: from,
+        start,
+        steps,
+    }
+}
+
+fn step<'a>(mapping: &'a Mapping, ranges: &IntervalSet) -> Step<'a> {
+    let mut moves = moves(ranges, &mapping.transformations);
+    moves.sort_by_key(|m| m.from);
+
+    Step {
+        mapping,
+        result: moves.iter().map(|m| m.to).collect(),
+        moves,
+    }
+}
+
+/// Splits `ranges` by the block made of `transforms`; the first transformation covering a
+/// number wins, numbers not covered by any pass through unchanged.
+pub(super) fn moves<'a>(ranges: &IntervalSet, transforms: &'a [Transformation]) -> Vec<Move<'a>> {
+    let mut unmapped = ranges.clone();
+    let mut moves = Vec::new();
+
+    for transformation in transforms {
+        let mask = IntervalSet::from(transformation.range);
+
+        moves.extend(unmapped.intersection(&mask).into_iter().map(|from| Move {
+            from,
+            to: from.shift(transformation.op),
+            transformation: Some(transformation),
+        }));
+        unmapped = unmapped.difference(&mask);
+    }
+
+    moves.extend(unmapped.into_iter().map(|from| Move {
+        from,
+        to: from,
+        transformation: None,
+    }));
+    moves
+}
+
+/// A single number on its own, anything longer as an interval.
+struct Values(Interval);
+
+impl fmt::Display for Values {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.len() {
+            1 => write!(f, "{}", self.0.start),
+            _ => write!(f, "{}", self.0),
+        }
+    }
+}
+
+struct Set<'a>(&'a IntervalSet);
+
+impl fmt::Display for Set<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.iter().collect::<Vec<_>>().as_slice() {
+            [only] => write!(f, "{}", Values(**only)),
+            _ => write!(f, "{}", self.0),
+        }
+    }
+}
+
+impl fmt::Display for Trace<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", self.category, Set(&self.start))?;
+
+        for step in &self.steps {
+            for m in &step.moves {
+                match m.transformation {
+                    Some(t) => {
+                        writeln!(f, "  {} -> {} by \"{}\"", Values(m.from), Values(m.to), t)?
+                    }
+                    None => writeln!(f, "  {} passes through", Values(m.from))?,
+                }
+            }
+            writeln!(f, "{} {}", step.mapping.destination, Set(&step.result))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day5::{Almanac, EXAMPLE, LOCATION, SEED};
+
+    #[test]
+    fn test_trace_seed() {
+        let input = EXAMPLE.parse::<Almanac>().unwrap();
+        let path = input.maps.path(SEED, LOCATION).unwrap();
+
+        // the walk of seed 79 spelled out in the puzzle description
+        let trace = trace(SEED, &path, IntervalSet::from(Interval::with_len(79, 1)));
+        let values: Vec<_> = trace
+            .steps
+            .iter()
+            .map(|step| step.result.min().unwrap())
+            .collect();
+        assert_eq!(values, [81, 81, 81, 74, 78, 78, 82]);
+
+        assert_eq!(
+            trace.to_string().lines().take(3).collect::<Vec<_>>(),
+            ["seed 79", "  79 -> 81 by \"52 50 48\"", "soil 81"]
+        );
+        assert!(trace
+            .to_string()
+            .contains("  81 passes through\nwater 81\n"));
+    }
+
+    #[test]
+    fn test_trace_ranges() {
+        let input = EXAMPLE.parse::<Almanac>().unwrap();
+        let path = input.maps.path(SEED, LOCATION).unwrap();
+
+        let seeds = input.seeds_as_ranges().unwrap();
+        let trace = trace(SEED, &path, seeds);
+
+        assert_eq!(trace.result().min(), Some(46));
+        for step in &trace.steps {
+            let moved: i128 = step.moves.iter().map(|m| m.from.len()).sum();
+            assert_eq!(moved, trace.start.count());
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt;
+
+use itertools::Itertools;
+
+use super::{CategoryGraph, Mapping, Transformation};
+use crate::{
+    interval::{Interval, IntervalSet},
+    solution::Severity,
+};
+
+/// Something in a block that parses fine but is probably not what the almanac meant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// Two lines claim the same numbers, so the one written first silently wins.
+    OverlappingSources {
+        block: String,
+        first: Transformation,
+        second: Transformation,
+        overlap: Interval,
+    },
+    /// Two lines send numbers to the same place, so the block is not one-to-one.
+    OverlappingDestinations {
+        block: String,
+        first: Transformation,
+        second: Transformation,
+        overlap: Interval,
+    },
+    /// Numbers between the source ranges that no line covers and that pass through unchanged.
+    Gap { block: String, gap: Interval },
+}
+
+impl Issue {
+    /// Gaps are common in real almanacs and harmless, overlaps make the answer depend on the
+    /// order of the lines.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Issue::Gap { .. } => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::OverlappingSources {
+                block,
+                first,
+                second,
+                overlap,
+            } => write!(
+                f,
+                "{}: source ranges of \"{}\" and \"{}\" overlap on {}",
+                block, first, second, overlap
+            ),
+            Issue::OverlappingDestinations {
+                block,
+                first,
+                second,
+                overlap,
+            } => write!(
+                f,
+                "{}: destination ranges of \"{}\" and \"{}\" overlap on {}",
+                block, first, second, overlap
+            ),
+            Issue::Gap { block, gap } => write!(
+                f,
+                "{}: {} lies between source ranges but is not covered",
+                block, gap
+            ),
+        }
+    }
+}
+
+/// Every issue in every block, in input order.
+pub fn validate(maps: &CategoryGraph) -> Vec<Issue> {
+    maps.mappings().iter().flat_map(validate_block).collect()
+}
+
+fn validate_block(mapping: &Mapping) -> Vec<Issue> {
+    let block = format!("{}-to-{} map", mapping.source, mapping.destination);
+    let mut issues = Vec::new();
+
+    for (first, second) in mapping.transformations.iter().tuple_combinations() {
+        if let Some(overlap) = first.range.intersection(&second.range) {
+            issues.push(Issue::OverlappingSources {
+                block: block.clone(),
+                first: first.clone(),
+                second: second.clone(),
+                overlap,
+            });
+        }
+
+        let destination = |t: &Transformation| t.range.shift(t.op);
+        if let Some(overlap) = destination(first).intersection(&destination(second)) {
+            issues.push(Issue::OverlappingDestinations {
+                block: block.clone(),
+                first: first.clone(),
+                second: second.clone(),
+                overlap,
+            });
+        }
+    }
+
+    let covered: IntervalSet = mapping.transformations.iter().map(|t| t.range).collect();
+    if let (Some(min), Some(max)) = (covered.min(), covered.max()) {
+        issues.extend(
+            covered
+                .complement(Interval::new(min, max + 1))
+                .into_iter()
+                .map(|gap| Issue::Gap {
+                    block: block.clone(),
+                    gap,
+                }),
+        );
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day5::{Almanac, EXAMPLE};
+
+    fn block(lines: &[&str]) -> CategoryGraph {
+        CategoryGraph::new(vec![Mapping {
+            source: "seed".to_string(),
+            destination: "soil".to_string(),
+            transformations: lines.iter().map(|line| line.parse().unwrap()).collect(),
+        }])
+    }
+
+    #[test]
+    fn test_example_is_valid() {
+        let input = EXAMPLE.parse::<Almanac>().unwrap();
+
+        assert_eq!(validate(&input.maps), []);
+    }
+
+    #[test]
+    fn test_issues() {
+        let issues = validate(&block(&["0 10 5", "3 12 5", "100 20 5"]));
+
+        assert_eq!(
+            issues.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            [
+                r#"seed-to-soil map: source ranges of "0 10 5" and "3 12 5" overlap on [12, 15)"#,
+                r#"seed-to-soil map: destination ranges of "0 10 5" and "3 12 5" overlap on [3, 5)"#,
+                "seed-to-soil map: [17, 20) lies between source ranges but is not covered",
+            ]
+        );
+    }
+}
@@ -1,95 +1,427 @@
+mod model;
+mod report;
+
+use std::{any::type_name, fmt, str::FromStr};
+
+use clap::Subcommand;
 use itertools::Itertools;
+#[cfg(feature = "bigint")]
+use num_bigint::BigUint;
+
+use crate::{prelude::*, report::Format};
+
+pub use model::{Physics, Quadratic, RaceModel};
+pub use report::{race_reports, RaceReport};
+
+/// The numbers of the single long race of part 2, whose digits are all joined together.
+#[cfg(feature = "bigint")]
+pub type LongNumber = BigUint;
+#[cfg(not(feature = "bigint"))]
+pub type LongNumber = u128;
+
+pub type LongRace = Record<LongNumber>;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    /// The parts read the sheet differently, so only the selected ones are parsed and a
+    /// mistake only one of them trips over does not fail the other.
+    type Input = (Option<Vec<Record>>, Option<LongRace>);
+    type Answer1 = u32;
+    type Answer2 = LongNumber;
+
+    fn parse(input: &str, parts: Parts) -> Result<Self::Input> {
+        Ok((
+            parts
+                .includes(1)
+                .then(|| parse_input_task1(input))
+                .transpose()?,
+            parts
+                .includes(2)
+                .then(|| parse_input_task2(input))
+                .transpose()?,
+        ))
+    }
+
+    fn part1(input: &Self::Input) -> Result<u32> {
+        task1(
+            input
+                .0
+                .as_ref()
+                .context("the races were not parsed for part 1")?,
+        )
+    }
 
-use crate::prelude::*;
+    fn part2(input: &Self::Input) -> Result<LongNumber> {
+        task2(
+            input
+                .1
+                .as_ref()
+                .context("the long race was not parsed for part 2")?,
+        )
+    }
+}
+
+fn task1(records: &[Record]) -> Result<u32> {
+    records.iter().try_fold(1u32, |product, record| {
+        let count = record.winning_window()?.map_or(0, |w| w.count());
+        let count = u32::try_from(count)
+            .with_context(|| format!("{} ways to win do not fit in a u32", count))?;
+
+        product
+            .checked_mul(count)
+            .context("the product of the ways to win does not fit in a u32")
+    })
+}
+
+pub fn task2(record: &LongRace) -> Result<LongNumber> {
+    Ok(record
+        .winning_window()?
+        .map_or(LongNumber::from(0u8), |w| w.count()))
+}
+
+/// Tools for inspecting the races, e.g. `aoc2023 day6 report`.
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Print how every race of part 1 can be won
+    Report {
+        #[arg(short, long, value_enum, default_value_t)]
+        format: Format,
+        /// Speed gained per ms of holding the button
+        #[arg(long, default_value_t = 1)]
+        rate: u128,
+        /// Speed the boat cannot charge beyond
+        #[arg(long)]
+        max_speed: Option<u128>,
+        /// Speed lost per ms once the boat is moving
+        #[arg(long, default_value_t = 0)]
+        friction: u128,
+    },
+}
+
+pub fn run_command(command: &Command, input: &str) -> Result<()> {
+    let records = parse_input_task1(input)?;
 
-pub fn run_day() -> Result<()> {
-    let input = include_str!("../input/6.txt");
+    match command {
+        Command::Report {
+            format,
+            rate,
+            max_speed,
+            friction,
+        } => {
+            let model = Physics {
+                rate: *rate,
+                max_speed: *max_speed,
+                friction: *friction,
+            };
+            let reports = race_reports(&records, &model);
+
+            match format {
+                Format::Text => report::print_table(&reports),
+                Format::Json => println!("{}", serde_json::to_string_pretty(&reports)?),
+            }
+        }
+    }
 
-    println!("Task 1: {}", task1(input)?);
-    println!("Task 2: {}", task2(input)?);
     Ok(())
 }
 
-fn task1(input: &str) -> Result<u32> {
-    let input = parse_input_task1(input)?
-        .iter()
-        .map(Record::breaking_distances)
-        .map(|d| d.len() as u32)
-        .product();
-    Ok(input)
+/// The hold times, in ms, that beat a record. Both ends are included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Window<N = u128> {
+    pub min: N,
+    pub max: N,
 }
 
-fn task2(input: &str) -> Result<u128> {
-    let record = parse_input_task2(input)?;
+impl<N: RaceNumber> Window<N> {
+    pub fn count(&self) -> N {
+        self.max
+            .checked_minus(&self.min)
+            .expect("window ends after it starts")
+            .plus(&N::from(1))
+    }
+}
 
-    Ok(record.breaking_distances().len() as u128)
+/// The integer operations the closed form needs, so `u128` and big integers share it.
+pub trait RaceNumber: Clone + Ord + fmt::Display + FromStr + From<u8> {
+    /// `None` when the product does not fit.
+    fn checked_times(&self, other: &Self) -> Option<Self>;
+    /// `None` when `other` is larger.
+    fn checked_minus(&self, other: &Self) -> Option<Self>;
+    fn plus(&self, other: &Self) -> Self;
+    /// Largest number whose square is at most `self`.
+    fn floor_sqrt(&self) -> Self;
+    fn is_odd(&self) -> bool;
+    fn half(&self) -> Self;
 }
 
-impl Record {
-    fn breaking_distances(&self) -> Vec<u128> {
-        (1..self.time)
-            .map(|held| (self.time - held) * held)
-            .filter(|distance| distance > &self.distance)
-            .collect()
+impl RaceNumber for u128 {
+    fn checked_times(&self, other: &Self) -> Option<Self> {
+        self.checked_mul(*other)
+    }
+
+    fn checked_minus(&self, other: &Self) -> Option<Self> {
+        self.checked_sub(*other)
+    }
+
+    fn plus(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn floor_sqrt(&self) -> Self {
+        self.isqrt()
+    }
+
+    fn is_odd(&self) -> bool {
+        self % 2 == 1
+    }
+
+    fn half(&self) -> Self {
+        self / 2
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl RaceNumber for BigUint {
+    fn checked_times(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn checked_minus(&self, other: &Self) -> Option<Self> {
+        (self >= other).then(|| self - other)
+    }
+
+    fn plus(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn floor_sqrt(&self) -> Self {
+        self.sqrt()
+    }
+
+    fn is_odd(&self) -> bool {
+        self.bit(0)
+    }
+
+    fn half(&self) -> Self {
+        self / 2u8
+    }
+}
+
+impl<N: RaceNumber> Record<N> {
+    /// Holding for `h` ms beats the record when `h * (time - h) > distance`, which is
+    /// `(2h - time)² < time² - 4 * distance`. Solved exactly on integers, so the window is the
+    /// largest `x = |2h - time|` below the square root with the same parity as `time`.
+    ///
+    /// Fails when `time²` does not fit in `N`.
+    pub fn winning_window(&self) -> Result<Option<Window<N>>> {
+        let Some(square) = self.time.checked_times(&self.time) else {
+            bail!(
+                "race of {} ms is too long, its square does not fit in a {}",
+                self.time,
+                type_name::<N>()
+            );
+        };
+        // a record past `time² / 4` cannot be beaten, even when 4 times it does not fit
+        let Some(discriminant) = self
+            .distance
+            .checked_times(&N::from(4))
+            .and_then(|four_distance| square.checked_minus(&four_distance))
+        else {
+            return Ok(None);
+        };
+
+        // x² has to stay strictly below the discriminant, so an exact root is one too many
+        let one = N::from(1);
+        let root = discriminant.floor_sqrt();
+        let x = match root.checked_times(&root).as_ref() == Some(&discriminant) {
+            true => root.checked_minus(&one),
+            false => Some(root),
+        };
+        let x = match x {
+            Some(x) if x.is_odd() != self.time.is_odd() => x.checked_minus(&one),
+            x => x,
+        };
+
+        // the window is symmetric around `time / 2`, so its end needs no addition
+        Ok(x.map(|x| {
+            let min = self.time.checked_minus(&x).expect("x is below time").half();
+            Window {
+                max: self.time.checked_minus(&min).expect("min is below time"),
+                min,
+            }
+        }))
     }
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
-struct Record {
-    time: u128,
-    distance: u128,
-}
-
-fn parse_input_task1(s: &str) -> Result<Vec<Record>> {
-    let mut lines = s.lines();
-
-    let times = lines
-        .next()
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .skip(1)
-                .map(|n| n.parse::<u128>().unwrap())
-        })
-        .unwrap();
-
-    let distance = lines
-        .next()
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .skip(1)
-                .map(|n| n.parse::<u128>().unwrap())
-        })
-        .unwrap();
+pub struct Record<N = u128> {
+    time: N,
+    distance: N,
+}
+
+/// The tokens after `label` on the line with the given 0-based index.
+fn labelled_line<'a>(s: &'a str, idx: usize, label: &str) -> Result<Vec<Token<'a>>, ParseError> {
+    let expected = format!("{:?}", label);
+    let line = s
+        .lines()
+        .nth(idx)
+        .ok_or_else(|| ParseError::end_of_input(idx + 1, &expected))?;
+
+    let mut tokens = Tokens::new(line);
+    let first = tokens.expect(&expected).map_err(|e| e.offset(idx))?;
+    if first.text != label {
+        return Err(first.error(&expected).offset(idx));
+    }
+
+    Ok(tokens.collect())
+}
+
+fn parse_input_task1(s: &str) -> Result<Vec<Record>, ParseError> {
+    let parse_line = |idx: usize, label: &str, expected: &str| {
+        labelled_line(s, idx, label)?
+            .iter()
+            .map(|token| token.parse::<u128>(expected).map_err(|e| e.offset(idx)))
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    let times = parse_line(0, "Time:", "race time")?;
+    let distances = parse_line(1, "Distance:", "record distance")?;
+
+    if times.len() != distances.len() {
+        let line = s.lines().nth(1).unwrap_or_default();
+        return Err(ParseError::new(
+            line.chars().count() + 1,
+            format!("{} record distances, one per race", times.len()),
+            &distances.len().to_string(),
+        )
+        .offset(1));
+    }
 
     Ok(times
-        .zip(distance)
+        .into_iter()
+        .zip(distances)
         .map(|(time, distance)| Record { time, distance })
         .collect())
 }
 
-fn parse_input_task2(s: &str) -> Result<Record> {
-    let parse_num = |line: &str| {
-        line.split_ascii_whitespace()
-            .skip(1)
-            .join("")
-            .parse::<u128>()
-    };
+/// The one long race of part 2, whose numbers are the digits of each line joined together.
+fn parse_input_task2<N: FromStr>(s: &str) -> Result<Record<N>, ParseError> {
+    let parse_num = |idx: usize, label: &str, expected: &str| {
+        let tokens = labelled_line(s, idx, label)?;
+        let column = tokens
+            .first()
+            .map(|token| token.column)
+            .unwrap_or_else(|| label.len() + 1);
+        let digits = tokens.iter().map(|token| token.text).join("");
 
-    let mut lines = s.lines();
-    let time = lines.next().map(parse_num).unwrap()?;
+        digits
+            .parse::<N>()
+            .map_err(|_| ParseError::new(column, expected, &digits).offset(idx))
+    };
 
-    let distance = lines.next().map(parse_num).unwrap()?;
+    let time = parse_num(0, "Time:", "race time")?;
+    let distance = parse_num(1, "Distance:", "record distance")?;
 
     Ok(Record { time, distance })
 }
 
+/// The example races from the puzzle description.
+pub const EXAMPLE: &str = r#"Time:      7  15   30
+Distance:  9  40  200"#;
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
+    /// Every hold time that beats the record, tried one by one.
+    fn breaking_holds(record: &Record) -> Vec<u128> {
+        (1..record.time)
+            .filter(|held| (record.time - held) * held > record.distance)
+            .collect()
+    }
+
+    #[test]
+    fn test_winning_window() {
+        let windows: Vec<_> = parse_input_task1(EXAMPLE)
+            .unwrap()
+            .iter()
+            .map(|record| record.winning_window().unwrap())
+            .collect();
+
+        assert_eq!(
+            windows,
+            [
+                Some(Window { min: 2, max: 5 }),
+                Some(Window { min: 4, max: 11 }),
+                // 10 and 20 only tie the record, the exact root of 100 must not count
+                Some(Window { min: 11, max: 19 }),
+            ]
+        );
+
+        let tie = Record {
+            time: 4,
+            distance: 4,
+        };
+        assert_eq!(tie.winning_window().unwrap(), None);
+
+        let err = Record {
+            time: 1 << 64,
+            distance: 1,
+        }
+        .winning_window()
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "race of 18446744073709551616 ms is too long, its square does not fit in a u128"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_window_matches_enumeration(time in 0u128..300, distance in 0u128..25_000) {
+            let record = Record { time, distance };
+            let holds = breaking_holds(&record);
+
+            let expected = holds.first().map(|&min| Window {
+                min,
+                max: *holds.last().unwrap(),
+            });
+            prop_assert_eq!(record.winning_window().unwrap(), expected);
+        }
+
+        #[test]
+        fn prop_window_boundaries(time in 0u128..1 << 64, permille in 0u128..=1000) {
+            // records up to the best distance of the race, so most of them can be beaten
+            let best = (time / 2) * (time - time / 2);
+            let record = Record { time, distance: best / 1000 * permille };
+            let beats = |held: u128| held * (time - held) > record.distance;
+
+            match record.winning_window().unwrap() {
+                Some(window) => {
+                    prop_assert!(beats(window.min) && beats(window.max));
+                    prop_assert!(!beats(window.min - 1));
+                    prop_assert!(window.max == time || !beats(window.max + 1));
+                }
+                None => prop_assert!(!beats(time / 2)),
+            }
+        }
+
+        #[test]
+        fn prop_long_race_fails(time in 1u128 << 64.., distance: u128) {
+            let record = Record { time, distance };
+
+            prop_assert!(record.winning_window().is_err());
+        }
+    }
+
     #[test]
     fn test_parse_input() {
-        let result = parse_input_task1(TEST).unwrap();
+        let result = parse_input_task1(EXAMPLE).unwrap();
 
         assert_eq!(
             result,
@@ -110,16 +442,34 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_input_task1("Time: 7 x\nDistance: 9 40").unwrap_err();
+        assert_eq!(err.to_string(), r#"1:9: expected race time, found "x""#);
+
+        let err = parse_input_task1("Time: 7 15\nDistance: 9").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"2:12: expected 2 record distances, one per race, found "1""#
+        );
+
+        let err = parse_input_task2::<u128>("Time: 7 15").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"2:1: expected "Distance:", found end of input"#
+        );
+    }
+
     #[test]
     fn test_task_1() {
-        let result = task1(TEST).unwrap();
+        let result = task1(&parse_input_task1(EXAMPLE).unwrap()).unwrap();
 
         assert_eq!(result, 288)
     }
 
     #[test]
     fn test_parse_input_task2() {
-        let result = parse_input_task2(TEST).unwrap();
+        let result = parse_input_task2::<u128>(EXAMPLE).unwrap();
 
         assert_eq!(
             result,
@@ -132,11 +482,38 @@ mod tests {
 
     #[test]
     fn test_task2() {
-        let result = task2(TEST).unwrap();
+        let result = task2(&parse_input_task2(EXAMPLE).unwrap()).unwrap();
 
-        assert_eq!(result, 71503)
+        assert_eq!(result, LongNumber::from(71503u32))
     }
 
-    const TEST: &str = r#"Time:      7  15   30
-Distance:  9  40  200"#;
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_task2_past_u128() {
+        // 2 * 10^30 ms, where only holding for exactly half of it beats 10^60 - 1
+        let input = format!("Time: 2{}\nDistance: {}", "0".repeat(30), "9".repeat(60));
+
+        let record = parse_input_task2::<BigUint>(&input).unwrap();
+        assert_eq!(task2(&record).unwrap(), BigUint::from(1u8));
+        assert!(
+            parse_input_task2::<u128>(&format!("Time: {}\nDistance: 1", "9".repeat(40))).is_err()
+        );
+    }
+
+    #[cfg(feature = "bigint")]
+    proptest! {
+        #[test]
+        fn prop_big_window_matches(time in 0u128..1 << 60, distance in 0u128..1 << 118) {
+            let big = Record {
+                time: BigUint::from(time),
+                distance: BigUint::from(distance),
+            };
+            let expected = Record { time, distance }.winning_window().unwrap().map(|w| Window {
+                min: BigUint::from(w.min),
+                max: BigUint::from(w.max),
+            });
+
+            prop_assert_eq!(big.winning_window().unwrap(), expected);
+        }
+    }
 }
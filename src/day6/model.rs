@@ -0,0 +1,261 @@
+use super::{Record, Window};
+
+/// How far a boat gets in a race of `time` ms after its button was held for `held` ms.
+pub trait RaceModel {
+    fn distance(&self, time: u128, held: u128) -> u128;
+
+    /// The shortest hold time that goes furthest, found by binary search. Like the default
+    /// [`winning_window`](RaceModel::winning_window), it needs a model whose distance rises to
+    /// a single peak and never rises again after it.
+    fn best_hold(&self, time: u128) -> u128 {
+        // first hold time where holding one ms longer stops paying off
+        partition_point(0, time, |held| {
+            self.distance(time, held + 1) > self.distance(time, held)
+        })
+    }
+
+    /// Every hold time that beats the record, binary searched on both sides of the peak.
+    fn winning_window(&self, record: &Record) -> Option<Window> {
+        search_window(self, record)
+    }
+}
+
+fn search_window<M: RaceModel + ?Sized>(model: &M, record: &Record) -> Option<Window> {
+    let beats = |held| model.distance(record.time, held) > record.distance;
+
+    let peak = model.best_hold(record.time);
+    if !beats(peak) {
+        return None;
+    }
+
+    Some(Window {
+        min: partition_point(0, peak, |held| !beats(held)),
+        max: partition_point(peak, record.time, |held| beats(held + 1)),
+    })
+}
+
+/// The first number in `[low, high]` for which `pred` is false, or `high` when there is none.
+/// `pred` has to be true up to some point and false after it.
+fn partition_point(mut low: u128, mut high: u128, pred: impl Fn(u128) -> bool) -> u128 {
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match pred(mid) {
+            true => low = mid + 1,
+            false => high = mid,
+        }
+    }
+
+    low
+}
+
+/// Every ms of holding adds `rate` mm/ms of speed, which then stays constant for the rest of
+/// the race. The puzzle itself is a rate of 1.
+///
+/// Holding for `h` ms travels `rate * h * (time - h)`, a parabola that opens downwards with
+/// its peak at `time / 2`. Since `rate * m > distance` exactly when `m > distance / rate`
+/// rounded down, the window is the one of the puzzle against a scaled down record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quadratic {
+    pub rate: u128,
+}
+
+impl RaceModel for Quadratic {
+    fn distance(&self, time: u128, held: u128) -> u128 {
+        // saturates at the longest distance a record can hold, which keeps the single peak
+        self.rate
+            .saturating_mul(held)
+            .saturating_mul(time.saturating_sub(held))
+    }
+
+    fn winning_window(&self, record: &Record) -> Option<Window> {
+        if self.rate == 0 {
+            return None;
+        }
+
+        let scaled = Record {
+            time: record.time,
+            distance: record.distance / self.rate,
+        };
+
+        // races too long for the closed form still have the binary search
+        match scaled.winning_window() {
+            Ok(window) => window,
+            Err(_) => search_window(self, record),
+        }
+    }
+}
+
+/// Holding charges `rate` mm/ms per ms up to an optional `max_speed`, and once released the
+/// boat loses `friction` mm/ms of speed every ms until it stops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Physics {
+    pub rate: u128,
+    pub max_speed: Option<u128>,
+    pub friction: u128,
+}
+
+impl RaceModel for Physics {
+    fn distance(&self, time: u128, held: u128) -> u128 {
+        let moving = time.saturating_sub(held);
+        let mut speed = self.rate.saturating_mul(held);
+        if let Some(max_speed) = self.max_speed {
+            speed = speed.min(max_speed);
+        }
+
+        // ms spent moving before friction stops the boat, its speed drops linearly until then
+        let ms = match self.friction {
+            0 => moving,
+            friction => moving.min(speed.div_ceil(friction)),
+        };
+        if ms == 0 {
+            return 0;
+        }
+
+        // the speed drops by less than it started at, and the distance is `ms` times the
+        // average of the first and last speed, so only the final product can overflow
+        let slowdown = self.friction * (ms - 1);
+        match slowdown % 2 {
+            0 => ms.saturating_mul(speed - slowdown / 2),
+            _ => (ms / 2).saturating_mul(speed.saturating_add(speed - slowdown)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// The window found by trying every hold time, checking that the winners are contiguous.
+    fn enumerate(model: &impl RaceModel, record: &Record) -> Option<Window> {
+        let winners: Vec<u128> = (0..=record.time)
+            .filter(|&held| model.distance(record.time, held) > record.distance)
+            .collect();
+
+        let window = Window {
+            min: *winners.first()?,
+            max: *winners.last()?,
+        };
+        assert_eq!(window.count(), winners.len() as u128, "winners have a gap");
+
+        Some(window)
+    }
+
+    #[test]
+    fn test_puzzle_is_quadratic() {
+        let record = Record {
+            time: 30,
+            distance: 200,
+        };
+
+        assert_eq!(
+            Quadratic { rate: 1 }.winning_window(&record),
+            record.winning_window().unwrap()
+        );
+        assert_eq!(
+            Physics {
+                rate: 1,
+                ..Physics::default()
+            }
+            .winning_window(&record),
+            record.winning_window().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_physics_distance() {
+        let capped = Physics {
+            rate: 2,
+            max_speed: Some(5),
+            friction: 0,
+        };
+        // 3 ms of holding would charge to 6, capped to 5 for the remaining 7 ms
+        assert_eq!(capped.distance(10, 3), 35);
+
+        let friction = Physics {
+            rate: 1,
+            max_speed: None,
+            friction: 2,
+        };
+        // released at 5 mm/ms, then 3 and 1 before stopping
+        assert_eq!(friction.distance(10, 5), 9);
+        // released at 6 mm/ms, then 3
+        let odd = Physics {
+            rate: 1,
+            max_speed: None,
+            friction: 3,
+        };
+        assert_eq!(odd.distance(10, 6), 9);
+    }
+
+    #[test]
+    fn test_distance_saturates() {
+        let record = Record {
+            time: 7,
+            distance: u128::MAX - 1,
+        };
+        let quadratic = Quadratic { rate: u128::MAX };
+        assert_eq!(quadratic.distance(7, 3), u128::MAX);
+        assert_eq!(quadratic.distance(7, 7), 0);
+        assert_eq!(
+            quadratic.winning_window(&record),
+            Some(Window { min: 1, max: 6 })
+        );
+
+        let physics = Physics {
+            rate: u128::MAX / 2,
+            max_speed: None,
+            friction: 1,
+        };
+        assert_eq!(physics.distance(7, 3), u128::MAX);
+        assert_eq!(
+            physics.winning_window(&record),
+            Some(Window { min: 1, max: 6 })
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_quadratic_matches_enumeration(
+            time in 0u128..80,
+            distance in 0u128..4_000,
+            rate in 0u128..6,
+        ) {
+            let record = Record { time, distance };
+            let model = Quadratic { rate };
+
+            prop_assert_eq!(model.winning_window(&record), enumerate(&model, &record));
+        }
+
+        #[test]
+        fn prop_physics_matches_enumeration(
+            time in 0u128..80,
+            distance in 0u128..4_000,
+            rate in 0u128..6,
+            max_speed in prop::option::of(0u128..60),
+            friction in 0u128..5,
+        ) {
+            let record = Record { time, distance };
+            let model = Physics { rate, max_speed, friction };
+
+            prop_assert_eq!(model.winning_window(&record), enumerate(&model, &record));
+        }
+
+        #[test]
+        fn prop_saturated_models_match_enumeration(
+            time in 0u128..80,
+            distance in any::<u128>(),
+            rate in any::<u128>(),
+            max_speed in prop::option::of(any::<u128>()),
+            friction in any::<u128>(),
+        ) {
+            let record = Record { time, distance };
+
+            let quadratic = Quadratic { rate };
+            prop_assert_eq!(quadratic.winning_window(&record), enumerate(&quadratic, &record));
+            let physics = Physics { rate, max_speed, friction };
+            prop_assert_eq!(physics.winning_window(&record), enumerate(&physics, &record));
+        }
+    }
+}
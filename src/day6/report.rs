@@ -0,0 +1,149 @@
+use serde::Serialize;
+
+use super::{RaceModel, Record};
+use crate::report::Table;
+
+/// How a single race can be won.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct RaceReport {
+    pub time: u128,
+    pub record: u128,
+    /// Shortest and longest hold times that beat the record, both included.
+    pub winning_min: Option<u128>,
+    pub winning_max: Option<u128>,
+    pub count: u128,
+    pub best_hold: u128,
+    pub best_distance: u128,
+    /// How far the best hold goes past the record, which is only beaten by a positive margin.
+    /// `None` when the difference does not fit in an `i128`.
+    pub margin: Option<i128>,
+}
+
+pub fn race_reports(records: &[Record], model: &impl RaceModel) -> Vec<RaceReport> {
+    records
+        .iter()
+        .map(|record| {
+            let window = model.winning_window(record);
+            let best_hold = model.best_hold(record.time);
+            let best_distance = model.distance(record.time, best_hold);
+
+            RaceReport {
+                time: record.time,
+                record: record.distance,
+                winning_min: window.as_ref().map(|w| w.min),
+                winning_max: window.as_ref().map(|w| w.max),
+                count: window.map_or(0, |w| w.count()),
+                best_hold,
+                best_distance,
+                margin: match best_distance.checked_sub(record.distance) {
+                    Some(ahead) => i128::try_from(ahead).ok(),
+                    None => 0i128.checked_sub_unsigned(record.distance - best_distance),
+                },
+            }
+        })
+        .collect()
+}
+
+pub fn print_table(reports: &[RaceReport]) {
+    let mut table = Table::new(&[
+        "Race",
+        "Time",
+        "Record",
+        "Winning holds",
+        "Count",
+        "Best hold",
+        "Best distance",
+        "Margin",
+    ]);
+
+    for (idx, report) in reports.iter().enumerate() {
+        let window = match (report.winning_min, report.winning_max) {
+            (Some(min), Some(max)) => format!("{}..={}", min, max),
+            _ => "-".to_string(),
+        };
+
+        table.push(vec![
+            (idx + 1).to_string(),
+            report.time.to_string(),
+            report.record.to_string(),
+            window,
+            report.count.to_string(),
+            report.best_hold.to_string(),
+            report.best_distance.to_string(),
+            report
+                .margin
+                .map_or("-".to_string(), |margin| format!("{:+}", margin)),
+        ]);
+    }
+
+    table.print();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day6::{parse_input_task1, Physics, Quadratic, EXAMPLE};
+
+    #[test]
+    fn test_race_reports() {
+        let records = parse_input_task1(EXAMPLE).unwrap();
+        let reports = race_reports(&records, &Quadratic { rate: 1 });
+
+        assert_eq!(
+            reports[0],
+            RaceReport {
+                time: 7,
+                record: 9,
+                winning_min: Some(2),
+                winning_max: Some(5),
+                count: 4,
+                best_hold: 3,
+                best_distance: 12,
+                margin: Some(3),
+            }
+        );
+        assert_eq!(reports.iter().map(|r| r.count).product::<u128>(), 288);
+    }
+
+    #[test]
+    fn test_unbeatable_race() {
+        let records = parse_input_task1("Time: 7\nDistance: 12").unwrap();
+        let slow = Physics {
+            rate: 1,
+            max_speed: Some(2),
+            friction: 0,
+        };
+
+        let report = &race_reports(&records, &slow)[0];
+        assert_eq!(report.count, 0);
+        assert_eq!(report.winning_min, None);
+        // holding 2 ms reaches the cap and leaves 5 ms to cover only 10
+        assert_eq!(
+            (report.best_hold, report.best_distance, report.margin),
+            (2, 10, Some(-2))
+        );
+
+        // matching the record does not beat it
+        let records = parse_input_task1("Time: 7\nDistance: 10").unwrap();
+        let report = &race_reports(&records, &slow)[0];
+        assert_eq!((report.count, report.margin), (0, Some(0)));
+    }
+
+    #[test]
+    fn test_margin_past_i128() {
+        let records = [Record {
+            time: 3,
+            distance: 0,
+        }];
+        let report = &race_reports(&records, &Quadratic { rate: u128::MAX })[0];
+        assert_eq!(report.best_distance, u128::MAX);
+        assert_eq!(report.margin, None);
+
+        let records = [Record {
+            time: 3,
+            distance: 1 << 127,
+        }];
+        let report = &race_reports(&records, &Quadratic { rate: 0 })[0];
+        assert_eq!(report.margin, Some(i128::MIN));
+    }
+}
@@ -1,35 +1,53 @@
-use std::{
-    cmp::Ordering,
-    collections::{HashMap, HashSet},
-};
+use std::{cmp::Ordering, collections::HashMap};
 
 use itertools::Itertools;
 
 use crate::prelude::*;
 
-pub fn run_day() -> Result<()> {
-    let input = include_str!("../input/7.txt");
-    println!("Task 1: {}", task1(input)?);
-    println!("Task 2: {}", task2(input)?);
-    Ok(())
-}
+pub struct Day7;
 
-fn task1(input: &str) -> Result<usize> {
-    let result = input
-        .lines()
-        .map(Bid::new)
-        .sorted()
-        .enumerate()
-        .map(|(idx, bid)| (idx + 1) * bid.amount as usize)
-        .sum();
+impl Solution for Day7 {
+    const DAY: u8 = 7;
 
-    Ok(result)
+    /// Each part plays by its own rules, so the hands are only parsed for the selected parts.
+    type Input = (Option<Vec<Bid>>, Option<Vec<Bid>>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str, parts: Parts) -> Result<Self::Input> {
+        let bids = |part, rules: Rules| {
+            parts
+                .includes(part)
+                .then(|| parse_bids(input, &rules))
+                .transpose()
+        };
+
+        Ok((bids(1, Rules::part1())?, bids(2, Rules::part2())?))
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        total_winnings(
+            input
+                .0
+                .as_ref()
+                .context("the bids were not parsed for part 1")?,
+        )
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        total_winnings(
+            input
+                .1
+                .as_ref()
+                .context("the bids were not parsed for part 2")?,
+        )
+    }
 }
 
-fn task2(input: &str) -> Result<usize> {
-    let result = input
-        .lines()
-        .map(Bid::new_t2)
+/// Each bid times its rank, weakest hand first, under whichever rules the bids were parsed with.
+pub fn total_winnings(bids: &[Bid]) -> Result<usize> {
+    let result = bids
+        .iter()
         .sorted()
         .enumerate()
         .map(|(idx, bid)| (idx + 1) * bid.amount as usize)
@@ -38,99 +56,146 @@ fn task2(input: &str) -> Result<usize> {
     Ok(result)
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
-struct Bid {
-    hand: Hand,
-    amount: u32,
+/// Where wild cards rank when two hands of the same type are compared card by card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WildRank {
+    /// Keep their place in the ordering
+    #[default]
+    Natural,
+    /// Weaker than every other card
+    Lowest,
+    /// Stronger than every other card
+    Highest,
 }
 
-impl Bid {
-    fn new(s: &str) -> Self {
-        let (first, second) = s.split_once(' ').unwrap();
+/// The card game being played: which cards exist, how they rank and which of them are wild.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    ordering: Vec<char>,
+    wild: Vec<char>,
+    wild_rank: WildRank,
+}
 
-        Self {
-            hand: Hand::new(first),
-            amount: second.parse::<u32>().unwrap(),
+impl Rules {
+    /// `ordering` lists every card from weakest to strongest, `wild` the cards that stand in
+    /// for whichever card makes the best hand.
+    pub fn new(ordering: &str, wild: &str, wild_rank: WildRank) -> Result<Self> {
+        let ordering: Vec<char> = ordering.chars().collect();
+        // card values start at 2 and wild cards may rank one past the strongest
+        if ordering.len() > MAX_CARDS {
+            bail!(
+                "ordering has {} cards, at most {} are supported",
+                ordering.len(),
+                MAX_CARDS
+            );
+        }
+        if let Some(card) = ordering.iter().duplicates().next() {
+            bail!("card {} appears more than once in the ordering", card);
         }
+        if let Some(card) = wild.chars().find(|card| !ordering.contains(card)) {
+            bail!("wild card {} is not in the ordering", card);
+        }
+
+        Ok(Self {
+            ordering,
+            wild: wild.chars().collect(),
+            wild_rank,
+        })
     }
 
-    fn new_t2(s: &str) -> Self {
-        let (first, second) = s.split_once(' ').unwrap();
+    /// Plain Camel Cards.
+    pub fn part1() -> Self {
+        Self::new(CARDS, "", WildRank::Natural).expect("valid rules")
+    }
 
-        Self {
-            hand: Hand::new_t2(first),
-            amount: second.parse::<u32>().unwrap(),
+    /// Jacks are jokers: wild, and the weakest card on their own.
+    pub fn part2() -> Self {
+        Self::new(CARDS, "J", WildRank::Lowest).expect("valid rules")
+    }
+
+    /// Strength of a single card starting at 2 for the weakest, 0 for cards not in the game.
+    fn card_value(&self, card: char) -> u8 {
+        let Some(idx) = self.ordering.iter().position(|c| *c == card) else {
+            return 0;
+        };
+
+        match self.wild_rank {
+            WildRank::Lowest if self.is_wild(card) => 1,
+            WildRank::Highest if self.is_wild(card) => self.ordering.len() as u8 + 2,
+            _ => idx as u8 + 2,
         }
     }
+
+    fn is_wild(&self, card: char) -> bool {
+        self.wild.contains(&card)
+    }
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
-struct Hand {
-    hand_type: Type,
-    card_values: [u8; 5],
+pub struct Bid {
+    hand: Hand,
+    amount: u32,
 }
 
-fn card_value(c: char) -> u8 {
-    match c {
-        '2' => 2,
-        '3' => 3,
-        '4' => 4,
-        '5' => 5,
-        '6' => 6,
-        '7' => 7,
-        '8' => 8,
-        '9' => 9,
-        'T' => 10,
-        'J' => 11,
-        'Q' => 12,
-        'K' => 13,
-        'A' => 14,
-        _ => 0,
-    }
+pub fn parse_bids(input: &str, rules: &Rules) -> Result<Vec<Bid>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| Bid::new(line, rules).map_err(|e| e.offset(idx)))
+        .collect()
 }
 
-fn card_value_t2(c: char) -> u8 {
-    match c {
-        '2' => 2,
-        '3' => 3,
-        '4' => 4,
-        '5' => 5,
-        '6' => 6,
-        '7' => 7,
-        '8' => 8,
-        '9' => 9,
-        'T' => 10,
-        'J' => 1,
-        'Q' => 12,
-        'K' => 13,
-        'A' => 14,
-        _ => 0,
+impl Bid {
+    /// A hand of five cards valid under `rules` and the bid amount.
+    fn new(s: &str, rules: &Rules) -> Result<Self, ParseError> {
+        let mut tokens = Tokens::new(s);
+
+        let hand = tokens.expect("hand of five cards")?;
+        if let Some((idx, c)) = hand
+            .text
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !rules.ordering.contains(c))
+        {
+            return Err(ParseError::new(
+                hand.column + idx,
+                format!("card, one of {}", rules.ordering.iter().collect::<String>()),
+                &c.to_string(),
+            ));
+        }
+        if hand.text.chars().count() != 5 {
+            return Err(hand.error("hand of five cards"));
+        }
+
+        let amount = tokens.parse_next::<u32>("bid amount")?;
+        tokens.finish()?;
+
+        Ok(Self {
+            hand: Hand::new(hand.text, rules),
+            amount,
+        })
     }
 }
 
-impl Hand {
-    fn new(cards: &str) -> Self {
-        let mut card_values = [0; 5];
-
-        for (idx, c) in cards.char_indices() {
-            card_values[idx] = card_value(c)
-        }
+const CARDS: &str = "23456789TJQKA";
+const MAX_CARDS: usize = u8::MAX as usize - 2;
 
-        Self {
-            hand_type: Type::from_cards(cards),
-            card_values,
-        }
-    }
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+struct Hand {
+    hand_type: Type,
+    card_values: [u8; 5],
+}
 
-    fn new_t2(cards: &str) -> Self {
+impl Hand {
+    fn new(cards: &str, rules: &Rules) -> Self {
         let mut card_values = [0; 5];
 
-        for (idx, c) in cards.char_indices() {
-            card_values[idx] = card_value_t2(c)
+        for (idx, c) in cards.chars().enumerate() {
+            card_values[idx] = rules.card_value(c)
         }
 
         Self {
-            hand_type: Type::from_cards_t2(cards),
+            hand_type: Type::from_cards(cards, rules),
             card_values,
         }
     }
@@ -148,26 +213,26 @@ enum Type {
 }
 
 impl Type {
-    fn from_cards(cards: &str) -> Self {
+    /// The best type the hand can make, with every wild card joining the largest group since
+    /// that always beats spreading them out.
+    fn from_cards(cards: &str, rules: &Rules) -> Self {
         let mut map = HashMap::new();
+        let mut wild = 0;
 
         for c in cards.chars() {
-            map.entry(c).and_modify(|count| *count += 1).or_insert(1);
+            match rules.is_wild(c) {
+                true => wild += 1,
+                false => *map.entry(c).or_insert(0) += 1,
+            }
         }
 
-        match *map.values().sorted().as_slice() {
-            [1, 1, 1, 2] => Self::Pair,
-            [1, 2, 2] => Self::TwoPair,
-            [1, 1, 3] => Self::ThreeOfAKind,
-            [2, 3] => Self::FullHouse,
-            [1, 4] => Self::FourOfAKind,
-            [5] => Self::FiveOfAKind,
-            _ => Self::HighCard,
+        let mut counts = map.into_values().sorted().collect_vec();
+        match counts.last_mut() {
+            Some(largest) => *largest += wild,
+            None => counts.push(wild),
         }
-    }
 
-    fn from_cards_t2(cards: &str) -> Self {
-        let to_type = |map: &HashMap<char, i32>| match *map.values().sorted().as_slice() {
+        match *counts.as_slice() {
             [1, 1, 1, 2] => Self::Pair,
             [1, 2, 2] => Self::TwoPair,
             [1, 1, 3] => Self::ThreeOfAKind,
@@ -175,21 +240,7 @@ impl Type {
             [1, 4] => Self::FourOfAKind,
             [5] => Self::FiveOfAKind,
             _ => Self::HighCard,
-        };
-
-        replace_joker(cards)
-            .into_iter()
-            .map(|hand| {
-                let mut map = HashMap::new();
-
-                for c in hand.chars() {
-                    map.entry(c).and_modify(|count| *count += 1).or_insert(1);
-                }
-
-                to_type(&map)
-            })
-            .max()
-            .unwrap_or(Type::FiveOfAKind)
+        }
     }
 
     fn value(&self) -> u8 {
@@ -205,19 +256,6 @@ impl Type {
     }
 }
 
-fn replace_joker(cards: &str) -> Vec<String> {
-    if !cards.contains('J') {
-        return vec![cards.to_string()];
-    }
-
-    let chars: HashSet<char> = cards.chars().filter(|c| *c != 'J').collect();
-
-    chars
-        .iter()
-        .map(|c| cards.replace('J', &c.to_string()))
-        .collect_vec()
-}
-
 impl PartialOrd for Type {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
@@ -230,6 +268,13 @@ impl Ord for Type {
     }
 }
 
+/// The example hands from the puzzle description.
+pub const EXAMPLE: &str = r#"32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483"#;
+
 #[cfg(test)]
 mod tests {
 
@@ -237,72 +282,149 @@ mod tests {
 
     #[test]
     fn test_task1() {
-        let res = task1(TEST).unwrap();
+        let res = total_winnings(&parse_bids(EXAMPLE, &Rules::part1()).unwrap()).unwrap();
         assert_eq!(res, 6440)
     }
 
     #[test]
     fn test_task2() {
-        let res = task2(TEST).unwrap();
+        let res = total_winnings(&parse_bids(EXAMPLE, &Rules::part2()).unwrap()).unwrap();
         assert_eq!(res, 5905)
     }
 
     #[test]
     fn test_ordering() {
+        let rules = Rules::part1();
+
         assert!(Type::FiveOfAKind > Type::FourOfAKind);
         assert!(Type::Pair < Type::FourOfAKind);
 
-        assert!(Hand::new("55555") > Hand::new("66662"));
-        assert!(Hand::new("T222A") > Hand::new("9111B"));
+        assert!(Hand::new("55555", &rules) > Hand::new("66662", &rules));
+        assert!(Hand::new("T222A", &rules) > Hand::new("9111B", &rules));
     }
 
     #[test]
     fn test_parse_hand() {
-        let hand = Hand::new("32T3K");
+        let hand = Hand::new("32T3K", &Rules::part1());
 
         assert_eq!(hand.hand_type, Type::Pair);
         assert_eq!(hand.card_values, [3, 2, 10, 3, 13]);
+
+        let hand = Hand::new("32T3J", &Rules::part2());
+        assert_eq!(hand.hand_type, Type::ThreeOfAKind);
+        assert_eq!(hand.card_values, [3, 2, 10, 3, 1]);
     }
 
     #[test]
     fn test_hand_type() {
-        assert_eq!(Type::from_cards("32T3K"), Type::Pair);
-        assert_eq!(Type::from_cards("T55J5"), Type::ThreeOfAKind);
-        assert_eq!(Type::from_cards("KK677"), Type::TwoPair);
-        assert_eq!(Type::from_cards("KTJJT"), Type::TwoPair);
-        assert_eq!(Type::from_cards("QQQJA"), Type::ThreeOfAKind);
-        assert_eq!(Type::from_cards("AAAKK"), Type::FullHouse);
-        assert_eq!(Type::from_cards("QQQQ2"), Type::FourOfAKind);
-        assert_eq!(Type::from_cards("55555"), Type::FiveOfAKind);
-        assert_eq!(Type::from_cards("12345"), Type::HighCard);
+        let rules = Rules::part1();
+
+        assert_eq!(Type::from_cards("32T3K", &rules), Type::Pair);
+        assert_eq!(Type::from_cards("T55J5", &rules), Type::ThreeOfAKind);
+        assert_eq!(Type::from_cards("KK677", &rules), Type::TwoPair);
+        assert_eq!(Type::from_cards("KTJJT", &rules), Type::TwoPair);
+        assert_eq!(Type::from_cards("QQQJA", &rules), Type::ThreeOfAKind);
+        assert_eq!(Type::from_cards("AAAKK", &rules), Type::FullHouse);
+        assert_eq!(Type::from_cards("QQQQ2", &rules), Type::FourOfAKind);
+        assert_eq!(Type::from_cards("55555", &rules), Type::FiveOfAKind);
+        assert_eq!(Type::from_cards("12345", &rules), Type::HighCard);
+    }
+
+    #[test]
+    fn test_wild_hand_type() {
+        let rules = Rules::part2();
+
+        assert_eq!(Type::from_cards("T55J5", &rules), Type::FourOfAKind);
+        assert_eq!(Type::from_cards("KTJJT", &rules), Type::FourOfAKind);
+        assert_eq!(Type::from_cards("KKJTT", &rules), Type::FullHouse);
+        assert_eq!(Type::from_cards("A9JJ2", &rules), Type::ThreeOfAKind);
+        assert_eq!(Type::from_cards("JJJJJ", &rules), Type::FiveOfAKind);
+        assert_eq!(Type::from_cards("2345J", &rules), Type::Pair);
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        // deuces wild but ranked where they stand, with aces low
+        let rules = Rules::new("A23456789TJQK", "2", WildRank::Natural).unwrap();
+
+        let hand = Hand::new("A2KKQ", &rules);
+        assert_eq!(hand.hand_type, Type::ThreeOfAKind);
+        assert_eq!(hand.card_values, [2, 3, 14, 14, 13]);
+
+        let highest = Rules::new(CARDS, "2", WildRank::Highest).unwrap();
+        assert!(Hand::new("2AAAK", &highest) > Hand::new("AAAAK", &highest));
+
+        let err = Rules::new("23452", "", WildRank::Natural).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "card 2 appears more than once in the ordering"
+        );
+        let err = Rules::new(CARDS, "X", WildRank::Lowest).unwrap_err();
+        assert_eq!(err.to_string(), "wild card X is not in the ordering");
+
+        let too_many: String = ('\u{100}'..).take(MAX_CARDS + 1).collect();
+        let err = Rules::new(&too_many, "", WildRank::Highest).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ordering has 254 cards, at most 253 are supported"
+        );
+        let most: String = ('\u{100}'..).take(MAX_CARDS).collect();
+        let rules = Rules::new(&most, "\u{100}", WildRank::Highest).unwrap();
+        assert_eq!(rules.card_value('\u{100}'), u8::MAX);
+        assert_eq!(rules.card_value('\u{101}'), 3);
+    }
+
+    #[test]
+    fn test_non_ascii_cards() {
+        let rules = Rules::new("♠♥♦♣A", "", WildRank::Natural).unwrap();
+
+        let bids = parse_bids("♠♥♦♣A 5", &rules).unwrap();
+        assert_eq!(bids[0].hand.card_values, [2, 3, 4, 5, 6]);
+        assert_eq!(bids[0].hand.hand_type, Type::HighCard);
+
+        let err = parse_bids("♠♥X♣A 5", &rules).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"1:3: expected card, one of ♠♥♦♣A, found "X""#
+        );
     }
 
     #[test]
     fn test_parse_bid() {
+        let rules = Rules::part1();
+
         assert_eq!(
-            Bid::new("32T3K 765"),
+            Bid::new("32T3K 765", &rules).unwrap(),
             Bid {
-                hand: Hand::new("32T3K"),
+                hand: Hand::new("32T3K", &rules),
                 amount: 765
             }
         );
     }
 
     #[test]
-    fn test_replace_joker() {
-        let mut res = replace_joker("KKJTT");
-        res.sort();
+    fn test_parse_bid_errors() {
+        let rules = Rules::part1();
 
-        assert_eq!(res, vec!["KKKTT", "KKTTT"]);
-        let mut res = replace_joker("A9JJ2");
-        res.sort();
+        let err = parse_bids("32T3K 765\nKK677 x", &rules).unwrap_err();
+        assert_eq!(err.to_string(), r#"2:7: expected bid amount, found "x""#);
 
-        assert_eq!(res, vec!["A9222", "A9992", "A9AA2"]);
-    }
+        let err = Bid::new("32T1K 765", &rules).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"1:4: expected card, one of 23456789TJQKA, found "1""#
+        );
 
-    const TEST: &str = r#"32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483"#;
+        let err = Bid::new("32T3 765", &rules).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"1:1: expected hand of five cards, found "32T3""#
+        );
+
+        let err = parse_bids("32T3K 765\n\nKK677 28", &rules).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2:1: expected hand of five cards, found end of line"
+        );
+    }
 }
@@ -3,22 +3,43 @@ use std::collections::{HashMap, HashSet};
 
 use crate::prelude::*;
 
-pub fn run_day() -> Result<()> {
-    let input = include_str!("../input/8.txt");
-    println!("Task 1: {}", task1(input)?);
-    println!("Task 2: {}", task2(input)?);
-    Ok(())
+type Network = HashMap<String, (String, String)>;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = (String, Network);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str, _parts: Parts) -> Result<Self::Input> {
+        Ok(parse_task1_input(input)?)
+    }
+
+    fn part1((instructions, map): &Self::Input) -> Result<usize> {
+        task1(instructions, map)
+    }
+
+    fn part2((instructions, map): &Self::Input) -> Result<usize> {
+        task2(instructions, map)
+    }
 }
 
-fn task1(input: &str) -> Result<usize> {
-    let (instructions, map) = parse_task1_input(input);
+fn task1(instructions: &str, map: &Network) -> Result<usize> {
+    for node in ["AAA", "ZZZ"] {
+        if !map.contains_key(node) {
+            bail!("there is no node {} in the network", node);
+        }
+    }
 
     let mut node = "AAA".to_string();
     let count = instructions
         .chars()
         .cycle()
         .take_while(|dir| {
-            node = map.get(&node).map(|lr| take_dir(lr, dir)).unwrap();
+            node = take_dir(&map[&node], dir);
             node != "ZZZ"
         })
         .count();
@@ -34,9 +55,7 @@ fn take_dir(tuple: &(String, String), c: &char) -> String {
     }
 }
 
-fn task2(input: &str) -> Result<usize> {
-    let (instructions, map) = parse_task1_input(input);
-
+pub fn task2(instructions: &str, map: &Network) -> Result<usize> {
     let start_positions: Vec<_> = map.keys().filter(|key| key.ends_with('A')).collect();
 
     let result = start_positions
@@ -48,7 +67,7 @@ fn task2(input: &str) -> Result<usize> {
                 .chars()
                 .cycle()
                 .take_while(|dir| {
-                    node = map.get(&node).map(|lr| take_dir(lr, dir)).unwrap();
+                    node = take_dir(&map[&node], dir);
                     !node.ends_with('Z')
                 })
                 .count()
@@ -66,18 +85,18 @@ fn prime_factors(n: usize) -> Vec<usize> {
     let mut result = Vec::new();
     let mut n = n;
 
-    while n % 2 == 0 {
+    while n.is_multiple_of(2) {
         result.push(2);
         n /= 2
     }
 
-    while n % 3 == 0 {
+    while n.is_multiple_of(3) {
         result.push(3);
         n /= 3
     }
 
     for i in (3..=(n / 2)).step_by(2) {
-        while n % i == 0 {
+        while n.is_multiple_of(i) {
             result.push(i);
             n /= i
         }
@@ -86,32 +105,79 @@ fn prime_factors(n: usize) -> Vec<usize> {
     result
 }
 
-fn parse_task1_input(input: &str) -> (String, HashMap<String, (String, String)>) {
-    let mut chunks = input.split("\n\n");
+fn parse_task1_input(input: &str) -> Result<(String, Network), ParseError> {
+    let mut chunks = blocks(input);
 
-    let instructions = chunks.next().unwrap();
-
-    let re = Regex::new(r"(\w+) = \((\w+), (\w+)\)").unwrap();
+    let (_, instructions) = chunks
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(1, "L/R instructions"))?;
+    let instructions = instructions.trim_end();
+    if let Some((idx, c)) = instructions
+        .chars()
+        .enumerate()
+        .find(|(_, c)| *c != 'L' && *c != 'R')
+    {
+        return Err(ParseError::new(idx + 1, "L or R", &c.to_string()));
+    }
 
-    let map = chunks
+    let (offset, nodes) = chunks
         .next()
-        .unwrap()
+        .ok_or_else(|| ParseError::end_of_input(3, "node definitions"))?;
+
+    let re = Regex::new(r"^(\w+) = \((\w+), (\w+)\)$").unwrap();
+
+    let lines = nodes
         .lines()
-        .map(|line| {
-            let caps = re.captures(line).unwrap();
+        .enumerate()
+        .map(|(idx, line)| {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let caps = re.captures(line.trim()).ok_or_else(|| {
+                let column = indent.chars().count() + 1;
+                ParseError::new(column, "node like \"AAA = (BBB, CCC)\"", line.trim())
+                    .offset(offset + idx)
+            })?;
+
+            Ok((offset + idx, indent, caps))
+        })
+        .collect::<Result<Vec<_>, ParseError>>()?;
+
+    let map: Network = lines
+        .iter()
+        .map(|(_, _, caps)| {
             (
-                caps.get(1).unwrap().as_str().to_string(),
-                (
-                    caps.get(2).unwrap().as_str().to_string(),
-                    caps.get(3).unwrap().as_str().to_string(),
-                ),
+                caps[1].to_string(),
+                (caps[2].to_string(), caps[3].to_string()),
             )
         })
         .collect();
 
-    (instructions.to_string(), map)
+    // every step has to land on a node, so a dangling reference is caught here
+    for (line, indent, caps) in &lines {
+        if let Some(next) = [caps.get(2), caps.get(3)]
+            .into_iter()
+            .flatten()
+            .find(|next| !map.contains_key(next.as_str()))
+        {
+            let column = indent.chars().count() + caps[0][..next.start()].chars().count() + 1;
+            return Err(ParseError::new(column, "defined node", next.as_str()).offset(*line));
+        }
+    }
+
+    Ok((instructions.to_string(), map))
 }
 
+/// The example network for part 2 from the puzzle description.
+pub const EXAMPLE_TASK2: &str = r#"LR
+
+    11A = (11B, XXX)
+    11B = (XXX, 11Z)
+    11Z = (11B, XXX)
+    22A = (22B, XXX)
+    22B = (22C, 22C)
+    22C = (22Z, 22Z)
+    22Z = (22B, 22B)
+    XXX = (XXX, XXX)"#;
+
 #[cfg(test)]
 mod tests {
 
@@ -119,25 +185,39 @@ mod tests {
 
     #[test]
     fn test_task1() {
-        let result = task1(TEST).unwrap();
+        let (instructions, map) = parse_task1_input(TEST).unwrap();
+        let result = task1(&instructions, &map).unwrap();
         assert_eq!(result, 2)
     }
 
     #[test]
     fn test_task1_other() {
-        let result = task1(TEST2).unwrap();
+        let (instructions, map) = parse_task1_input(TEST2).unwrap();
+        let result = task1(&instructions, &map).unwrap();
         assert_eq!(result, 6)
     }
 
     #[test]
     fn test_task2() {
-        let result = task2(TASK2_TEST).unwrap();
+        let (instructions, map) = parse_task1_input(EXAMPLE_TASK2).unwrap();
+        let result = task2(&instructions, &map).unwrap();
         assert_eq!(result, 6)
     }
 
+    #[test]
+    fn test_task1_missing_ends() {
+        let (instructions, map) = parse_task1_input("RL\n\nBBB = (BBB, BBB)").unwrap();
+        let err = task1(&instructions, &map).unwrap_err();
+        assert_eq!(err.to_string(), "there is no node AAA in the network");
+
+        let (instructions, map) = parse_task1_input("RL\n\nAAA = (AAA, AAA)").unwrap();
+        let err = task1(&instructions, &map).unwrap_err();
+        assert_eq!(err.to_string(), "there is no node ZZZ in the network");
+    }
+
     #[test]
     fn test_parse_task1() {
-        let (instructions, map) = parse_task1_input(TEST);
+        let (instructions, map) = parse_task1_input(TEST).unwrap();
 
         assert_eq!(instructions, "RL");
         assert_eq!(map.len(), 7);
@@ -147,6 +227,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_task1_input("RLX\n\nAAA = (BBB, CCC)").unwrap_err();
+        assert_eq!(err.to_string(), r#"1:3: expected L or R, found "X""#);
+
+        let err = parse_task1_input("RL\n\nAAA = (BBB, CCC)\n  BBB = BBB").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"4:3: expected node like "AAA = (BBB, CCC)", found "BBB = BBB""#
+        );
+
+        let err = parse_task1_input("RL\n\nAAA = (BBB, CCC)x").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"3:1: expected node like "AAA = (BBB, CCC)", found "AAA = (BBB, CCC)x""#
+        );
+
+        let err = parse_task1_input("RL\n\n  AAA = (AAA, CCC)").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"3:15: expected defined node, found "CCC""#
+        );
+
+        let err = parse_task1_input("RL").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "3:1: expected node definitions, found end of input"
+        );
+    }
+
     const TEST: &str = r#"RL
 
     AAA = (BBB, CCC)
@@ -162,15 +272,4 @@ mod tests {
     AAA = (BBB, BBB)
     BBB = (AAA, ZZZ)
     ZZZ = (ZZZ, ZZZ)"#;
-
-    const TASK2_TEST: &str = r#"LR
-
-    11A = (11B, XXX)
-    11B = (XXX, 11Z)
-    11Z = (11B, XXX)
-    22A = (22B, XXX)
-    22B = (22C, 22C)
-    22C = (22Z, 22Z)
-    22Z = (22B, 22B)
-    XXX = (XXX, XXX)"#;
 }
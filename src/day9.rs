@@ -1,38 +1,48 @@
 use crate::prelude::*;
 
-pub fn run_day() -> Result<()> {
-    let input = include_str!("../input/9.txt");
+pub struct Day9;
 
-    println!("task 1: {}", task1(input)?);
-    println!("task 2: {}", task2(input)?);
+impl Solution for Day9 {
+    const DAY: u8 = 9;
 
-    Ok(())
+    type Input = Vec<Vec<i32>>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str, _parts: Parts) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<i32> {
+        task1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<i32> {
+        task2(input)
+    }
 }
 
-fn task1(input: &str) -> Result<i32> {
-    let result = input
+fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    input
         .lines()
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .map(|c| c.parse::<i32>().unwrap())
-                .collect::<Vec<i32>>()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            Tokens::new(line)
+                .map(|token| token.parse::<i32>("number").map_err(|e| e.offset(idx)))
+                .collect()
         })
-        .map(|seq| predict_next(&seq))
-        .sum();
+        .collect()
+}
+
+pub fn task1(sequences: &[Vec<i32>]) -> Result<i32> {
+    let result = sequences.iter().map(|seq| predict_next(seq)).sum();
 
     Ok(result)
 }
 
-fn task2(input: &str) -> Result<i32> {
-    let result = input
-        .lines()
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .map(|c| c.parse::<i32>().unwrap())
-                .collect::<Vec<i32>>()
-        })
-        .map(|seq: Vec<i32>| predict_back(&seq))
-        .sum();
+fn task2(sequences: &[Vec<i32>]) -> Result<i32> {
+    let result = sequences.iter().map(|seq| predict_back(seq)).sum();
 
     Ok(result)
 }
@@ -63,13 +73,19 @@ fn predict_back(seq: &[i32]) -> i32 {
         )
 }
 
+/// The example sequences from the puzzle description.
+pub const EXAMPLE: &str = r#"0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+    "#;
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_task1() {
-        let result = task1(TEST).unwrap();
+        let result = task1(&parse_input(EXAMPLE).unwrap()).unwrap();
 
         assert_eq!(result, 114);
     }
@@ -93,15 +109,17 @@ mod tests {
         assert_eq!(predict_next(&test1), 28);
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_input("0 3 6\n1 3 x 10").unwrap_err();
+
+        assert_eq!(err.to_string(), r#"2:5: expected number, found "x""#);
+    }
+
     #[test]
     fn test_task_2() {
-        let result = task2(TEST).unwrap();
+        let result = task2(&parse_input(EXAMPLE).unwrap()).unwrap();
 
         assert_eq!(result, 2);
     }
-
-    const TEST: &str = r#"0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45
-    "#;
 }
@@ -0,0 +1,91 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::prelude::*;
+
+/// Where a day's puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// `input/N.txt`, relative to the working directory.
+    Default,
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    pub fn from_arg(arg: Option<&Path>) -> Self {
+        match arg {
+            None => Source::Default,
+            Some(path) if path == Path::new("-") => Source::Stdin,
+            Some(path) => Source::File(path.to_path_buf()),
+        }
+    }
+
+    /// How the input is referred to in error messages, e.g. `input/7.txt`.
+    pub fn name(&self, day: u8) -> String {
+        match self {
+            Source::Default => default_path(day).display().to_string(),
+            Source::File(path) => path.display().to_string(),
+            Source::Stdin => "<stdin>".to_string(),
+        }
+    }
+
+    pub fn read(&self, day: u8) -> Result<String> {
+        match self {
+            Source::Default => read_file(&default_path(day)),
+            Source::File(path) => read_file(path),
+            Source::Stdin => {
+                let mut buf = String::new();
+                io::stdin()
+                    .read_to_string(&mut buf)
+                    .context("could not read input from stdin")?;
+                Ok(buf)
+            }
+        }
+    }
+}
+
+pub fn default_path(day: u8) -> PathBuf {
+    Path::new("input").join(format!("{}.txt", day))
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path)
+        .with_context(|| format!("could not read input file {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(Source::from_arg(None), Source::Default);
+        assert_eq!(Source::from_arg(Some(Path::new("-"))), Source::Stdin);
+        assert_eq!(
+            Source::from_arg(Some(Path::new("other.txt"))),
+            Source::File(PathBuf::from("other.txt"))
+        );
+    }
+
+    #[test]
+    fn test_name() {
+        assert_eq!(Source::Default.name(7), "input/7.txt");
+        assert_eq!(Source::Stdin.name(7), "<stdin>");
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let err = Source::File("does/not/exist.txt".into())
+            .read(5)
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "could not read input file does/not/exist.txt"
+        );
+    }
+}
@@ -0,0 +1,354 @@
+use std::{fmt, ops::Range};
+
+use serde::Serialize;
+
+/// Half-open interval `[start, end)`. Any interval with `start >= end` is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Interval {
+    pub start: i128,
+    pub end: i128,
+}
+
+impl Interval {
+    pub fn new(start: i128, end: i128) -> Self {
+        Self { start, end }
+    }
+
+    pub fn with_len(start: i128, len: i128) -> Self {
+        Self::new(start, start + len)
+    }
+
+    pub fn len(&self) -> i128 {
+        (self.end - self.start).max(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, n: i128) -> bool {
+        self.start <= n && n < self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let overlap = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    pub fn shift(&self, offset: i128) -> Interval {
+        Interval::new(self.start + offset, self.end + offset)
+    }
+}
+
+impl From<Range<i128>> for Interval {
+    fn from(range: Range<i128>) -> Self {
+        Interval::new(range.start, range.end)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+/// A set of integers stored as sorted, disjoint, non-adjacent and non-empty intervals.
+///
+/// Every constructor and operation normalizes, so two sets holding the same integers are equal.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of disjoint intervals in the set.
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of integers in the set.
+    pub fn count(&self) -> i128 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn min(&self) -> Option<i128> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    pub fn max(&self) -> Option<i128> {
+        self.intervals.last().map(|interval| interval.end - 1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval> + '_ {
+        self.intervals.iter()
+    }
+
+    pub fn contains(&self, n: i128) -> bool {
+        let idx = self.intervals.partition_point(|interval| interval.end <= n);
+        self.intervals
+            .get(idx)
+            .is_some_and(|interval| interval.contains(n))
+    }
+
+    /// Every member moved by `offset`.
+    pub fn shift(&self, offset: i128) -> IntervalSet {
+        Self {
+            intervals: self
+                .intervals
+                .iter()
+                .map(|interval| interval.shift(offset))
+                .collect(),
+        }
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        *self = self.union(&IntervalSet::from(interval));
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.iter().chain(other.iter()).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            if let Some(overlap) = a.intersection(b) {
+                result.push(overlap);
+            }
+
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        // disjoint inputs give disjoint, sorted overlaps, but they can touch
+        result.into_iter().collect()
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        match (self.min(), self.max()) {
+            (Some(min), Some(max)) => {
+                self.intersection(&other.complement(Interval::new(min, max + 1)))
+            }
+            _ => IntervalSet::new(),
+        }
+    }
+
+    /// Every integer in `bounds` that is not in the set.
+    pub fn complement(&self, bounds: Interval) -> IntervalSet {
+        let mut result = Vec::new();
+        let mut cursor = bounds.start;
+
+        for interval in self.iter() {
+            if interval.start > cursor {
+                result.push(Interval::new(cursor, interval.start.min(bounds.end)));
+            }
+            cursor = cursor.max(interval.end);
+        }
+        result.push(Interval::new(cursor, bounds.end));
+
+        result.into_iter().collect()
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        std::iter::once(interval).collect()
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut sorted: Vec<Interval> = iter
+            .into_iter()
+            .filter(|interval| !interval.is_empty())
+            .collect();
+        sorted.sort();
+
+        let mut intervals: Vec<Interval> = Vec::with_capacity(sorted.len());
+        for interval in sorted {
+            match intervals.last_mut() {
+                // overlapping or adjacent, coalesce into the previous one
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => intervals.push(interval),
+            }
+        }
+
+        Self { intervals }
+    }
+}
+
+impl IntoIterator for IntervalSet {
+    type Item = Interval;
+    type IntoIter = std::vec::IntoIter<Interval>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a IntervalSet {
+    type Item = &'a Interval;
+    type IntoIter = std::slice::Iter<'a, Interval>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (idx, interval) in self.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", interval)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn set(intervals: &[(i128, i128)]) -> IntervalSet {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn test_normalize() {
+        let set = set(&[(5, 8), (0, 2), (2, 3), (7, 10), (20, 20), (15, 12)]);
+
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![Interval::new(0, 3), Interval::new(5, 10)]
+        );
+        assert_eq!(set.count(), 8);
+        assert_eq!(set.min(), Some(0));
+        assert_eq!(set.max(), Some(9));
+    }
+
+    #[test]
+    fn test_contains() {
+        let set = set(&[(0, 3), (5, 10)]);
+
+        assert!(set.contains(0));
+        assert!(!set.contains(3));
+        assert!(set.contains(9));
+        assert!(!set.contains(10));
+        assert!(!set.contains(-1));
+    }
+
+    #[test]
+    fn test_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25)]);
+
+        assert_eq!(a.union(&b), set(&[(0, 30)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25)]));
+        assert_eq!(a.difference(&b), set(&[(0, 5), (25, 30)]));
+        assert_eq!(
+            a.complement(Interval::new(-5, 35)),
+            set(&[(-5, 0), (10, 20), (30, 35)])
+        );
+        assert_eq!(a.complement(Interval::new(2, 8)), IntervalSet::new());
+        assert_eq!(a.shift(-5), set(&[(-5, 5), (15, 25)]));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(set(&[(5, 8), (0, 2)]).to_string(), "{[0, 2), [5, 8)}");
+    }
+
+    const DOMAIN: std::ops::Range<i128> = -40..50;
+
+    fn arb_set() -> impl Strategy<Value = IntervalSet> {
+        prop::collection::vec((-30i128..30, 0i128..15), 0..6).prop_map(|intervals| {
+            intervals
+                .into_iter()
+                .map(|(start, len)| Interval::with_len(start, len))
+                .collect()
+        })
+    }
+
+    fn is_normalized(set: &IntervalSet) -> bool {
+        set.iter().all(|interval| !interval.is_empty())
+            && set
+                .intervals
+                .windows(2)
+                .all(|pair| pair[0].end < pair[1].start)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_union(a in arb_set(), b in arb_set()) {
+            let result = a.union(&b);
+
+            prop_assert!(is_normalized(&result));
+            for n in DOMAIN {
+                prop_assert_eq!(result.contains(n), a.contains(n) || b.contains(n));
+            }
+        }
+
+        #[test]
+        fn prop_intersection(a in arb_set(), b in arb_set()) {
+            let result = a.intersection(&b);
+
+            prop_assert!(is_normalized(&result));
+            for n in DOMAIN {
+                prop_assert_eq!(result.contains(n), a.contains(n) && b.contains(n));
+            }
+        }
+
+        #[test]
+        fn prop_difference(a in arb_set(), b in arb_set()) {
+            let result = a.difference(&b);
+
+            prop_assert!(is_normalized(&result));
+            for n in DOMAIN {
+                prop_assert_eq!(result.contains(n), a.contains(n) && !b.contains(n));
+            }
+        }
+
+        #[test]
+        fn prop_complement(a in arb_set(), start in -35i128..35, len in 0i128..40) {
+            let bounds = Interval::with_len(start, len);
+            let result = a.complement(bounds);
+
+            prop_assert!(is_normalized(&result));
+            for n in DOMAIN {
+                prop_assert_eq!(result.contains(n), bounds.contains(n) && !a.contains(n));
+            }
+        }
+
+        #[test]
+        fn prop_count(a in arb_set()) {
+            let members = DOMAIN.filter(|&n| a.contains(n)).count() as i128;
+
+            prop_assert_eq!(a.count(), members);
+        }
+    }
+}
@@ -0,0 +1,12 @@
+pub mod check;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod input;
+pub mod interval;
+pub mod prelude;
+pub mod report;
+pub mod solution;
+pub mod timing;
@@ -1,34 +1,298 @@
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod prelude;
+use std::{ops::RangeInclusive, path::PathBuf};
 
-use clap::Parser;
-use prelude::*;
+use aoc2023::{
+    check::Answers,
+    day5, day6,
+    input::Source,
+    prelude::*,
+    report::{self, DayReport, Format},
+    solution::{self, Parts, Run, Runner, Severity},
+    timing::format_duration,
+};
+use clap::{Parser, Subcommand};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
-    #[arg(short, long)]
+    #[arg(short, long, conflicts_with_all = ["all", "days"])]
     day: Option<u8>,
+
+    /// Run every registered day
+    #[arg(short, long, conflicts_with_all = ["days", "input"])]
+    all: bool,
+
+    /// Run every registered day in a range, e.g. `5..=8`
+    #[arg(long, value_parser = solution::parse_day_range, conflicts_with = "input")]
+    days: Option<RangeInclusive<u8>>,
+
+    /// Puzzle input to use, `-` reads from stdin [default: input/<day>.txt]
+    #[arg(short, long, global = true)]
+    input: Option<PathBuf>,
+
+    /// Which part of the puzzle to solve
+    #[arg(short, long, value_enum, default_value_t = Parts::Both)]
+    part: Parts,
+
+    /// Repeat every phase N times and report min/median/mean/max timings
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    bench: Option<u32>,
+
+    /// Worker threads for days that solve in parallel [default: one per CPU]
+    #[arg(short = 'j', long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    threads: Option<u32>,
+
+    /// Compare the answers against the known correct ones and fail on a mismatch
+    #[arg(long)]
+    check: bool,
+
+    /// Known correct answers used by `--check`
+    #[arg(long, default_value = "answers.toml", requires = "check")]
+    answers: PathBuf,
+
+    /// Check the input for suspicious content before solving, failing on errors
+    #[arg(long)]
+    validate: bool,
+
+    /// How to print the results
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Day specific tools that inspect a puzzle instead of solving it.
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Inspect the almanac of day 5
+    Day5 {
+        #[command(subcommand)]
+        command: day5::Command,
+    },
+    /// Inspect the races of day 6
+    Day6 {
+        #[command(subcommand)]
+        command: day6::Command,
+    },
+}
+
+impl Command {
+    fn day(&self) -> u8 {
+        match self {
+            Command::Day5 { .. } => 5,
+            Command::Day6 { .. } => 6,
+        }
+    }
+
+    fn run(&self, input: &str) -> Result<()> {
+        match self {
+            Command::Day5 { command } => day5::run_command(command, input),
+            Command::Day6 { command } => day6::run_command(command, input),
+        }
+    }
+}
+
+impl Cli {
+    fn runners(&self) -> Result<Vec<&'static dyn Runner>> {
+        if self.all {
+            return solution::in_range(0..=u8::MAX);
+        }
+
+        if let Some(days) = &self.days {
+            return solution::in_range(days.clone());
+        }
+
+        match self.day {
+            Some(day) => Ok(vec![solution::find(day)?]),
+            None => Ok(vec![solution::latest()]),
+        }
+    }
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    if let Some(threads) = cli.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads as usize)
+            .build_global()?;
+    }
+    let source = Source::from_arg(cli.input.as_deref());
+
+    if let Some(command) = &cli.command {
+        let input = source.read(command.day())?;
+        return command
+            .run(&input)
+            .map_err(|err| in_file(err, &source, command.day()));
+    }
+
+    let runners = cli.runners()?;
+    let answers = match cli.check {
+        true => Some(Answers::load(&cli.answers)?),
+        false => None,
+    };
+
+    let reports: Vec<DayReport> = runners
+        .iter()
+        .map(|runner| {
+            let outcome = run_day(*runner, &source, &cli, answers.as_ref());
+            if let Err(err) = &outcome {
+                if cli.format == Format::Text && runners.len() > 1 {
+                    eprintln!("Day {} failed: {:#}", runner.day(), err);
+                }
+            }
+
+            DayReport {
+                day: runner.day(),
+                outcome,
+            }
+        })
+        .collect();
+
+    match cli.format {
+        Format::Json => report::print_json(&reports, answers.as_ref())?,
+        Format::Text if cli.all || cli.days.is_some() => {
+            report::print_summary(&reports, answers.as_ref())
+        }
+        Format::Text => {}
+    }
+
+    let mismatches = count_mismatches(&reports, answers.as_ref());
+
+    let total = reports.len();
+    let mut errors: Vec<Error> = reports
+        .into_iter()
+        .filter_map(|report| report.outcome.err())
+        .collect();
+
+    match errors.len() {
+        0 => {}
+        1 if total == 1 => return Err(errors.remove(0)),
+        failed => bail!("{} of {} days failed", failed, total),
+    }
+
+    if mismatches > 0 {
+        bail!("{} answer(s) did not match the known answers", mismatches);
+    }
+
+    Ok(())
+}
+
+fn run_day(
+    runner: &dyn Runner,
+    source: &Source,
+    cli: &Cli,
+    answers: Option<&Answers>,
+) -> Result<Run> {
+    let text = cli.format == Format::Text;
+    if text {
+        println!("Running Day {}", runner.day());
+    }
+
+    let input = source.read(runner.day())?;
+    if cli.validate {
+        validate(runner, &input, source)?;
+    }
+
+    let iterations = cli.bench.unwrap_or(1) as usize;
+    let run = runner
+        .run(&input, cli.part, iterations)
+        .map_err(|err| in_file(err, source, runner.day()))?;
+
+    if !text {
+        return Ok(run);
+    }
 
     println!(
-        "Running Day {}",
-        cli.day.map_or("latest".to_string(), |n| n.to_string())
+        "Parsed in {}",
+        format_duration(run.parse_time.stats().median)
     );
-    match cli.day.unwrap_or(0) {
-        5 => day5::run_day()?,
-        6 => day6::run_day()?,
-        7 => day7::run_day()?,
-        8 => day8::run_day()?,
-        _ => day9::run_day()?,
+    for answer in &run.answers {
+        print!(
+            "Task {}: {} ({})",
+            answer.part,
+            answer.value,
+            format_duration(answer.time.stats().median)
+        );
+
+        match answers {
+            Some(answers) => {
+                let verdict = answers.verdict(runner.day(), answer.part, &answer.value);
+                println!(" {}", verdict.label());
+
+                if let Some(diff) = verdict.diff() {
+                    for line in diff.lines() {
+                        println!("    {}", line);
+                    }
+                }
+            }
+            None => println!(),
+        }
+    }
+
+    if cli.bench.is_some() {
+        let serial = match rayon::current_num_threads() {
+            1 => None,
+            _ => Some(single_threaded(|| {
+                runner.run(&input, cli.part, iterations)
+            })?),
+        };
+
+        report::print_bench(runner.day(), &run, serial.as_ref());
+    }
+
+    Ok(run)
+}
+
+/// Prints every finding to stderr, so they never mix with JSON output.
+fn validate(runner: &dyn Runner, input: &str, source: &Source) -> Result<()> {
+    let findings = runner
+        .validate(input)
+        .map_err(|err| in_file(err, source, runner.day()))?;
+
+    for finding in &findings {
+        eprintln!("{}", finding);
+    }
+
+    let errors = findings
+        .iter()
+        .filter(|finding| finding.severity == Severity::Error)
+        .count();
+    if errors > 0 {
+        bail!(
+            "{} failed validation with {} error(s)",
+            source.name(runner.day()),
+            errors
+        );
     }
 
     Ok(())
 }
+
+/// Runs `f` on a pool of one thread, as the baseline the speedup of `--bench` is measured against.
+fn single_threaded<T: Send>(f: impl FnOnce() -> Result<T> + Send) -> Result<T> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()?
+        .install(f)
+}
+
+/// Prefixes the position of a parse error with the file it happened in.
+fn in_file(err: Error, source: &Source, day: u8) -> Error {
+    match err.downcast::<ParseError>() {
+        Ok(err) => err.in_file(source.name(day)).into(),
+        Err(err) => err,
+    }
+}
+
+fn count_mismatches(reports: &[DayReport], answers: Option<&Answers>) -> usize {
+    let Some(answers) = answers else {
+        return 0;
+    };
+
+    reports
+        .iter()
+        .filter_map(|report| Some((report.day, report.outcome.as_ref().ok()?)))
+        .flat_map(|(day, run)| run.answers.iter().map(move |answer| (day, answer)))
+        .filter(|(day, answer)| answers.verdict(*day, answer.part, &answer.value).failed())
+        .count()
+}
@@ -1 +1,227 @@
-pub use anyhow::{bail, Error, Result};
+use std::{fmt, str::FromStr};
+
+pub use anyhow::{bail, Context, Error, Result};
+
+pub use crate::solution::{Parts, Solution};
+
+/// A position in the puzzle input that could not be parsed, with what was expected there.
+///
+/// Lines and columns are 1-based. Parsers of a single line or block report positions relative
+/// to that text and callers [`offset`](ParseError::offset) them into the whole input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    /// `found` token on the first line at `column`.
+    pub fn new(column: usize, expected: impl Into<String>, found: &str) -> Self {
+        Self {
+            file: None,
+            line: 1,
+            column,
+            expected: expected.into(),
+            found: format!("{:?}", found),
+        }
+    }
+
+    /// Ran out of tokens on the first line, `column` is just past its last character.
+    pub fn end_of_line(column: usize, expected: impl Into<String>) -> Self {
+        Self {
+            found: "end of line".to_string(),
+            ..Self::new(column, expected, "")
+        }
+    }
+
+    /// Ran out of lines, `line` is the 1-based number of the missing line.
+    pub fn end_of_input(line: usize, expected: impl Into<String>) -> Self {
+        Self {
+            found: "end of input".to_string(),
+            ..Self::new(1, expected, "").offset(line - 1)
+        }
+    }
+
+    /// Moves the error down by `lines`, for text that starts `lines` lines into the input.
+    pub fn offset(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    /// Names the file the input came from, printed in front of the position.
+    pub fn in_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+
+        write!(
+            f,
+            "{}:{}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A whitespace separated token and the 1-based column it starts at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub column: usize,
+    pub text: &'a str,
+}
+
+impl Token<'_> {
+    pub fn parse<T: FromStr>(&self, expected: &str) -> Result<T, ParseError> {
+        self.text.parse().map_err(|_| self.error(expected))
+    }
+
+    pub fn error(&self, expected: &str) -> ParseError {
+        ParseError::new(self.column, expected, self.text)
+    }
+}
+
+/// Splits a line on ASCII whitespace, keeping track of where each token starts.
+pub struct Tokens<'a> {
+    line: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Tokens<'a> {
+    pub fn new(line: &'a str) -> Self {
+        Self { line, rest: line }
+    }
+
+    /// The next token, or an error pointing just past the end of the line.
+    pub fn expect(&mut self, expected: &str) -> Result<Token<'a>, ParseError> {
+        self.next()
+            .ok_or_else(|| ParseError::end_of_line(self.line.chars().count() + 1, expected))
+    }
+
+    pub fn parse_next<T: FromStr>(&mut self, expected: &str) -> Result<T, ParseError> {
+        self.expect(expected)?.parse(expected)
+    }
+
+    /// Errors on any token left on the line.
+    pub fn finish(&mut self) -> Result<(), ParseError> {
+        match self.next() {
+            Some(token) => Err(token.error("end of line")),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let trimmed = self
+            .rest
+            .trim_start_matches(|c: char| c.is_ascii_whitespace());
+        if trimmed.is_empty() {
+            self.rest = trimmed;
+            return None;
+        }
+
+        let len = trimmed
+            .find(|c: char| c.is_ascii_whitespace())
+            .unwrap_or(trimmed.len());
+        let start = self.line.len() - trimmed.len();
+
+        self.rest = &trimmed[len..];
+
+        Some(Token {
+            column: self.line[..start].chars().count() + 1,
+            text: &trimmed[..len],
+        })
+    }
+}
+
+/// Splits the input on blank lines, pairing each block with the number of lines before it.
+pub fn blocks(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut offset = 0;
+
+    input.split("\n\n").filter_map(move |block| {
+        let trimmed = block.trim_start_matches('\n');
+        let start = offset + block.len() - trimmed.len();
+        offset += block.matches('\n').count() + 2;
+
+        (!trimmed.trim().is_empty()).then_some((start, trimmed))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens() {
+        let tokens: Vec<_> = Tokens::new("  ab  c\td").collect();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token {
+                    column: 3,
+                    text: "ab"
+                },
+                Token {
+                    column: 7,
+                    text: "c"
+                },
+                Token {
+                    column: 9,
+                    text: "d"
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_token_errors() {
+        let mut tokens = Tokens::new("32T3K x");
+        tokens.expect("hand").unwrap();
+
+        let err = tokens.parse_next::<u32>("bid amount").unwrap_err();
+        assert_eq!(err.to_string(), r#"1:7: expected bid amount, found "x""#);
+
+        let err = tokens.parse_next::<u32>("bid amount").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1:8: expected bid amount, found end of line"
+        );
+    }
+
+    #[test]
+    fn test_error_position() {
+        let err = ParseError::new(7, "bid amount", "x")
+            .offset(11)
+            .in_file("input/7.txt");
+
+        assert_eq!(
+            err.to_string(),
+            r#"input/7.txt:12:7: expected bid amount, found "x""#
+        );
+        assert_eq!(
+            ParseError::end_of_input(2, "distances").to_string(),
+            "2:1: expected distances, found end of input"
+        );
+    }
+
+    #[test]
+    fn test_blocks() {
+        let blocks: Vec<_> = blocks("a\n\nb\nc\n\n\nd\n").collect();
+
+        assert_eq!(blocks, vec![(0, "a"), (2, "b\nc"), (6, "d\n")]);
+    }
+}
@@ -0,0 +1,283 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    check::Answers,
+    prelude::*,
+    solution::Run,
+    timing::{format_duration, Samples},
+};
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Human readable progress and tables
+    #[default]
+    Text,
+    /// A JSON array with one object per day and part
+    Json,
+}
+
+/// Outcome of running a single day, collected for the summary printed after `--all`.
+pub struct DayReport {
+    pub day: u8,
+    pub outcome: Result<Run>,
+}
+
+/// Plain text table with left aligned columns sized to their widest cell.
+pub struct Table {
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(header: &[&str]) -> Self {
+        Self {
+            rows: vec![header.iter().map(|h| h.to_string()).collect()],
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    pub fn print(&self) {
+        let widths: Vec<usize> = (0..self.rows[0].len())
+            .map(|col| {
+                self.rows
+                    .iter()
+                    .map(|row| row[col].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        for (idx, row) in self.rows.iter().enumerate() {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join(" | ");
+            println!("{}", line.trim_end());
+
+            if idx == 0 {
+                let separator = widths
+                    .iter()
+                    .map(|width| "-".repeat(*width))
+                    .collect::<Vec<_>>()
+                    .join("-+-");
+                println!("{}", separator);
+            }
+        }
+    }
+}
+
+pub fn print_summary(reports: &[DayReport], answers: Option<&Answers>) {
+    let mut table = match answers {
+        Some(_) => Table::new(&["Day", "Part", "Answer", "Time", "Check"]),
+        None => Table::new(&["Day", "Part", "Answer", "Time"]),
+    };
+    let check_cell = |row: &mut Vec<String>, cell: &str| {
+        if answers.is_some() {
+            row.push(cell.to_string());
+        }
+    };
+
+    for report in reports {
+        match &report.outcome {
+            Ok(run) => {
+                let mut row = vec![
+                    report.day.to_string(),
+                    "parse".to_string(),
+                    String::new(),
+                    format_duration(run.parse_time.stats().median),
+                ];
+                check_cell(&mut row, "");
+                table.push(row);
+
+                for answer in &run.answers {
+                    let mut row = vec![
+                        report.day.to_string(),
+                        answer.part.to_string(),
+                        answer.value.clone(),
+                        format_duration(answer.time.stats().median),
+                    ];
+                    if let Some(answers) = answers {
+                        let verdict = answers.verdict(report.day, answer.part, &answer.value);
+                        row.push(verdict.label().to_string());
+                    }
+                    table.push(row);
+                }
+            }
+            Err(err) => {
+                let mut row = vec![
+                    report.day.to_string(),
+                    "-".to_string(),
+                    format!("error: {}", err),
+                    "-".to_string(),
+                ];
+                check_cell(&mut row, "-");
+                table.push(row);
+            }
+        }
+    }
+
+    println!();
+    table.print();
+}
+
+/// Min/median/mean/max of every phase of a `--bench` run. With a `serial` run of the same
+/// phases on a single thread, adds how much faster the median got with more threads.
+pub fn print_bench(day: u8, run: &Run, serial: Option<&Run>) {
+    let mut columns = vec!["Phase", "Min", "Median", "Mean", "Max"];
+    if serial.is_some() {
+        columns.push("Speedup");
+    }
+    let mut table = Table::new(&columns);
+
+    for (idx, (phase, samples)) in phases(run).enumerate() {
+        let mut row = bench_row(phase, samples);
+        if let Some((_, baseline)) = serial.and_then(|serial| phases(serial).nth(idx)) {
+            row.push(speedup(baseline, samples));
+        }
+        table.push(row);
+    }
+
+    println!();
+    println!(
+        "Day {} over {} iterations",
+        day,
+        run.parse_time.iterations()
+    );
+    table.print();
+}
+
+fn phases(run: &Run) -> impl Iterator<Item = (String, &Samples)> {
+    std::iter::once(("parse".to_string(), &run.parse_time)).chain(
+        run.answers
+            .iter()
+            .map(|answer| (format!("part {}", answer.part), &answer.time)),
+    )
+}
+
+/// Ratio of the medians, e.g. `3.10x` when `samples` took a third of the `baseline` time.
+fn speedup(baseline: &Samples, samples: &Samples) -> String {
+    let parallel = samples.stats().median.as_secs_f64();
+
+    match parallel > 0.0 {
+        true => format!("{:.2}x", baseline.stats().median.as_secs_f64() / parallel),
+        false => "-".to_string(),
+    }
+}
+
+fn bench_row(phase: String, samples: &Samples) -> Vec<String> {
+    let stats = samples.stats();
+
+    vec![
+        phase,
+        format_duration(stats.min),
+        format_duration(stats.median),
+        format_duration(stats.mean),
+        format_duration(stats.max),
+    ]
+}
+
+/// One answered part, or a failed day, as emitted by `--format json`.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct JsonRecord {
+    pub day: u8,
+    pub part: Option<u8>,
+    pub answer: Option<String>,
+    pub answer_type: Option<&'static str>,
+    pub parse_duration_ns: Option<u64>,
+    pub duration_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    pub error: Option<String>,
+}
+
+pub fn json_records(reports: &[DayReport], answers: Option<&Answers>) -> Vec<JsonRecord> {
+    let median_ns = |samples: &Samples| samples.stats().median.as_nanos() as u64;
+
+    reports
+        .iter()
+        .flat_map(|report| match &report.outcome {
+            Ok(run) => run
+                .answers
+                .iter()
+                .map(|answer| JsonRecord {
+                    day: report.day,
+                    part: Some(answer.part),
+                    answer: Some(answer.value.clone()),
+                    answer_type: Some(answer.value_type),
+                    parse_duration_ns: Some(median_ns(&run.parse_time)),
+                    duration_ns: Some(median_ns(&answer.time)),
+                    check: answers.map(|answers| {
+                        answers
+                            .verdict(report.day, answer.part, &answer.value)
+                            .label()
+                    }),
+                    expected: answers.and_then(|answers| answers.expected(report.day, answer.part)),
+                    error: None,
+                })
+                .collect(),
+            Err(err) => vec![JsonRecord {
+                day: report.day,
+                part: None,
+                answer: None,
+                answer_type: None,
+                parse_duration_ns: None,
+                duration_ns: None,
+                check: None,
+                expected: None,
+                error: Some(format!("{:#}", err)),
+            }],
+        })
+        .collect()
+}
+
+pub fn print_json(reports: &[DayReport], answers: Option<&Answers>) -> Result<()> {
+    let records = json_records(reports, answers);
+    println!("{}", serde_json::to_string_pretty(&records)?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{self, Parts};
+
+    #[test]
+    fn test_json_records() {
+        let reports = [
+            DayReport {
+                day: 9,
+                outcome: solution::find(9)
+                    .unwrap()
+                    .run("0 3 6 9 12 15", Parts::One, 1),
+            },
+            DayReport {
+                day: 5,
+                outcome: Err(anyhow::anyhow!("could not read input file input/5.txt")),
+            },
+        ];
+
+        let records = json_records(&reports, None);
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].part, Some(1));
+        assert_eq!(records[0].answer.as_deref(), Some("18"));
+        assert_eq!(records[0].answer_type, Some("i32"));
+        assert_eq!(records[0].error, None);
+        assert_eq!(records[1].part, None);
+        assert_eq!(
+            records[1].error.as_deref(),
+            Some("could not read input file input/5.txt")
+        );
+
+        let json = serde_json::to_value(&records[1]).unwrap();
+        assert!(json.get("check").is_none());
+    }
+}
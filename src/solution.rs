@@ -0,0 +1,251 @@
+use std::{any::type_name, fmt::Display, ops::RangeInclusive};
+
+use clap::ValueEnum;
+use itertools::Itertools;
+
+use crate::{
+    day5::Day5, day6::Day6, day7::Day7, day8::Day8, day9::Day9, prelude::*, timing::Samples,
+};
+
+/// A single day's puzzle: the input is parsed once and the selected parts are solved from it.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    /// Days whose parts read the input differently only need to parse what `parts` selects.
+    fn parse(input: &str, parts: Parts) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+
+    /// Problems in an input that parses but might not mean what the puzzle expects.
+    fn validate(_input: &Self::Input) -> Vec<Finding> {
+        Vec::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// One problem reported by [`Solution::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+
+        write!(f, "{}: {}", label, self.message)
+    }
+}
+
+/// Which parts of a day to run.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Parts {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    #[default]
+    Both,
+}
+
+impl Parts {
+    pub fn includes(&self, part: u8) -> bool {
+        match self {
+            Parts::One => part == 1,
+            Parts::Two => part == 2,
+            Parts::Both => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub part: u8,
+    pub value: String,
+    /// Rust type the solution produced the answer as, e.g. `i128`.
+    pub value_type: &'static str,
+    pub time: Samples,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub parse_time: Samples,
+    pub answers: Vec<Answer>,
+}
+
+/// Object safe view of a [`Solution`], so that days can live side by side in the registry.
+pub trait Runner: Sync {
+    fn day(&self) -> u8;
+    /// Parses `input` and solves the selected parts, repeating every phase `iterations` times.
+    fn run(&self, input: &str, parts: Parts, iterations: usize) -> Result<Run>;
+    /// Parses `input` and checks it with [`Solution::validate`].
+    fn validate(&self, input: &str) -> Result<Vec<Finding>>;
+}
+
+impl<S: Solution + Sync> Runner for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn run(&self, input: &str, parts: Parts, iterations: usize) -> Result<Run> {
+        let (input, parse_time) = Samples::measure(iterations, || S::parse(input, parts))?;
+        let mut answers = Vec::new();
+
+        if parts.includes(1) {
+            let (value, time) = Samples::measure(iterations, || S::part1(&input))?;
+            answers.push(Answer {
+                part: 1,
+                value: value.to_string(),
+                value_type: type_name::<S::Answer1>(),
+                time,
+            });
+        }
+
+        if parts.includes(2) {
+            let (value, time) = Samples::measure(iterations, || S::part2(&input))?;
+            answers.push(Answer {
+                part: 2,
+                value: value.to_string(),
+                value_type: type_name::<S::Answer2>(),
+                time,
+            });
+        }
+
+        Ok(Run {
+            parse_time,
+            answers,
+        })
+    }
+
+    fn validate(&self, input: &str) -> Result<Vec<Finding>> {
+        Ok(S::validate(&S::parse(input, Parts::Both)?))
+    }
+}
+
+static REGISTRY: &[&dyn Runner] = &[&Day5, &Day6, &Day7, &Day8, &Day9];
+
+pub fn find(day: u8) -> Result<&'static dyn Runner> {
+    match REGISTRY.iter().find(|runner| runner.day() == day) {
+        Some(runner) => Ok(*runner),
+        None => bail!(
+            "day {} is not implemented, available days: {}",
+            day,
+            REGISTRY.iter().map(|runner| runner.day()).join(", ")
+        ),
+    }
+}
+
+/// Every registered day within `days`, in ascending order.
+pub fn in_range(days: RangeInclusive<u8>) -> Result<Vec<&'static dyn Runner>> {
+    let runners = REGISTRY
+        .iter()
+        .copied()
+        .filter(|runner| days.contains(&runner.day()))
+        .sorted_by_key(|runner| runner.day())
+        .collect_vec();
+
+    if runners.is_empty() {
+        bail!(
+            "no days in {}..={} are implemented, available days: {}",
+            days.start(),
+            days.end(),
+            REGISTRY.iter().map(|runner| runner.day()).join(", ")
+        );
+    }
+
+    Ok(runners)
+}
+
+/// Parses `5..=8`, `5..9` or a single `5` into an inclusive range of days.
+pub fn parse_day_range(s: &str) -> Result<RangeInclusive<u8>> {
+    let range = if let Some((start, end)) = s.split_once("..=") {
+        start.trim().parse()?..=end.trim().parse()?
+    } else if let Some((start, end)) = s.split_once("..") {
+        let end: u8 = end.trim().parse()?;
+        let Some(end) = end.checked_sub(1) else {
+            bail!("empty day range {}", s)
+        };
+        start.trim().parse()?..=end
+    } else {
+        let day = s.trim().parse()?;
+        day..=day
+    };
+
+    if range.is_empty() {
+        bail!("empty day range {}", s);
+    }
+
+    Ok(range)
+}
+
+pub fn latest() -> &'static dyn Runner {
+    *REGISTRY
+        .iter()
+        .max_by_key(|runner| runner.day())
+        .expect("registry contains at least one day")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(7).unwrap().day(), 7);
+        assert!(find(1).is_err());
+    }
+
+    #[test]
+    fn test_run_selected_part() {
+        let answers = find(9)
+            .unwrap()
+            .run("0 3 6 9 12 15", Parts::Two, 1)
+            .unwrap()
+            .answers;
+
+        assert_eq!(answers.len(), 1);
+        assert_eq!(answers[0].part, 2);
+        assert_eq!(answers[0].value, "-3");
+        assert_eq!(answers[0].value_type, "i32");
+    }
+
+    #[test]
+    fn test_in_range() {
+        let days = in_range(0..=7)
+            .unwrap()
+            .iter()
+            .map(|r| r.day())
+            .collect_vec();
+        assert_eq!(days, vec![5, 6, 7]);
+
+        assert!(in_range(10..=20).is_err());
+    }
+
+    #[test]
+    fn test_parse_day_range() {
+        assert_eq!(parse_day_range("5..=8").unwrap(), 5..=8);
+        assert_eq!(parse_day_range("5..8").unwrap(), 5..=7);
+        assert_eq!(parse_day_range("6").unwrap(), 6..=6);
+        assert!(parse_day_range("8..=5").is_err());
+        assert!(parse_day_range("5..5").is_err());
+        assert!(parse_day_range("five").is_err());
+    }
+
+    #[test]
+    fn test_latest() {
+        assert_eq!(latest().day(), 9);
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::prelude::*;
+
+/// Wall-clock times of every repetition of a single phase (parse, part 1 or part 2).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Samples(Vec<Duration>);
+
+impl Samples {
+    /// Runs `f` `iterations` times (at least once), returning the last result and every timing.
+    pub fn measure<T>(iterations: usize, mut f: impl FnMut() -> Result<T>) -> Result<(T, Self)> {
+        let mut durations = Vec::with_capacity(iterations.max(1));
+        let mut result = None;
+
+        for _ in 0..iterations.max(1) {
+            let start = Instant::now();
+            let value = black_box(f()?);
+            durations.push(start.elapsed());
+
+            result = Some(value);
+        }
+
+        Ok((result.expect("measured at least once"), Self(durations)))
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn stats(&self) -> Stats {
+        let mut sorted = self.0.clone();
+        sorted.sort();
+
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: sorted.iter().sum::<Duration>() / sorted.len() as u32,
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let mut calls = 0;
+        let (result, samples) = Samples::measure(3, || {
+            calls += 1;
+            Ok(calls)
+        })
+        .unwrap();
+
+        assert_eq!(result, 3);
+        assert_eq!(samples.iterations(), 3);
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples = Samples([4, 1, 3, 2].map(Duration::from_millis).to_vec());
+
+        assert_eq!(
+            samples.stats(),
+            Stats {
+                min: Duration::from_millis(1),
+                median: Duration::from_micros(2500),
+                mean: Duration::from_micros(2500),
+                max: Duration::from_millis(4),
+            }
+        );
+    }
+}
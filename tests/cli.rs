@@ -0,0 +1,342 @@
+use std::{
+    fs,
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+fn aoc(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc2023"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .expect("failed to run aoc2023")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).to_string()
+}
+
+/// The answers printed as `Task N: <answer> (<time>)`, in order.
+fn answers(output: &Output) -> Vec<String> {
+    stdout(output)
+        .lines()
+        .filter_map(|line| line.strip_prefix("Task "))
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Writes `content` to a file unique to the calling test, so tests can run in parallel.
+fn temp_input(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("aoc2023-{}-{}.txt", name, std::process::id()));
+    fs::write(&path, content).unwrap();
+    path
+}
+
+fn assert_day(day: &str, expected: [&str; 2]) {
+    let output = aoc(&["--day", day]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with(&format!("Running Day {}\n", day)));
+    assert_eq!(answers(&output), expected);
+}
+
+#[test]
+fn day5() {
+    assert_day("5", ["340994526", "52210644"]);
+}
+
+#[test]
+fn day6() {
+    assert_day("6", ["1195150", "42550411"]);
+}
+
+#[test]
+fn day7() {
+    assert_day("7", ["250957639", "251515496"]);
+}
+
+#[test]
+fn day8() {
+    assert_day("8", ["19631", "21003205388413"]);
+}
+
+#[test]
+fn day9() {
+    assert_day("9", ["1681758908", "803"]);
+}
+
+#[test]
+fn latest_day() {
+    let output = aoc(&["--part", "1"]);
+
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Running Day 9\n"));
+}
+
+#[test]
+fn check_passes() {
+    let output = aoc(&["--days", "7..=9", "--part", "1", "--check"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output).matches(" PASS").count(), 6);
+}
+
+#[test]
+fn summary_for_single_day_range() {
+    let output = aoc(&["--days", "9", "--part", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Day | Part"));
+
+    let output = aoc(&["--day", "9", "--part", "1"]);
+    assert!(!stdout(&output).contains("Day | Part"));
+}
+
+#[test]
+fn input_from_stdin() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc2023"))
+        .args(["--day", "9", "--input", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"0 3 6 9 12 15\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(answers(&output), ["18", "-3"]);
+}
+
+#[test]
+fn unknown_day() {
+    let output = aoc(&["--day", "1"]);
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("day 1 is not implemented, available days: 5, 6, 7, 8, 9"));
+}
+
+#[test]
+fn missing_input_file() {
+    let output = aoc(&["--day", "7", "--input", "does/not/exist.txt"]);
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("could not read input file does/not/exist.txt"));
+}
+
+#[test]
+fn malformed_input_file() {
+    let path = temp_input("malformed", "32T3K 765\nKK677 x\n");
+    let output = aoc(&["--day", "7", "--input", path.to_str().unwrap()]);
+    fs::remove_file(&path).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains(&format!(
+        r#"{}:2:7: expected bid amount, found "x""#,
+        path.display()
+    )));
+}
+
+#[test]
+fn failing_day_in_range() {
+    let dir = std::env::temp_dir().join(format!("aoc2023-range-{}", std::process::id()));
+    fs::create_dir_all(dir.join("input")).unwrap();
+    fs::write(dir.join("input/9.txt"), "0 3 6 9 12 15\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc2023"))
+        .args(["--days", "8..=9"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(!output.status.success());
+    assert_eq!(answers(&output), ["18", "-3"]);
+    assert!(stderr(&output).contains("Day 8 failed: could not read input file input/8.txt"));
+    assert!(stderr(&output).contains("1 of 2 days failed"));
+}
+
+#[test]
+fn json_output() {
+    let output = aoc(&["--days", "8..=9", "--part", "1", "--format", "json"]);
+    assert!(output.status.success());
+
+    let records: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["day"], 8);
+    assert_eq!(records[0]["answer"], "19631");
+    assert_eq!(records[1]["answer_type"], "i32");
+}
+
+#[test]
+fn day5_compose() {
+    let output = aoc(&["day5", "compose"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).ends_with("lowest location for the seed ranges: 52210644\n"));
+}
+
+#[test]
+fn day5_seeds() {
+    let output = aoc(&["day5", "seeds", "52210644"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("of which planted: {[385733775, 385733776)}\n"));
+}
+
+#[test]
+fn day5_trace() {
+    let output = aoc(&["day5", "trace", "385733775"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("seed 385733775\n"));
+    assert!(stdout(&output).ends_with("location 52210644\n"));
+}
+
+#[test]
+fn validate_day5() {
+    let output = aoc(&["--day", "5", "--validate"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).contains(
+        "warning: soil-to-fertilizer map: [3137177175, 3891730222) lies between source ranges"
+    ));
+
+    let path = temp_input(
+        "overlapping",
+        "seeds: 1 2\n\nseed-to-soil map:\n0 10 5\n3 12 5\n",
+    );
+    let output = aoc(&[
+        "--day",
+        "5",
+        "--validate",
+        "--input",
+        path.to_str().unwrap(),
+    ]);
+    fs::remove_file(&path).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("error: seed-to-soil map: source ranges"));
+    assert!(stderr(&output).contains("failed validation with 2 error(s)"));
+}
+
+#[test]
+fn bench_reports_speedup() {
+    let output = aoc(&["--day", "5", "--threads", "2", "--bench", "2"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(answers(&output), ["340994526", "52210644"]);
+    assert!(stdout(&output).contains("Speedup"));
+
+    let output = aoc(&["--day", "5", "--threads", "1", "--bench", "2"]);
+    assert!(!stdout(&output).contains("Speedup"));
+}
+
+#[test]
+fn day5_export() {
+    let output = aoc(&["day5", "export"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let stages: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stages.len(), 8);
+    assert_eq!(stages[7]["category"], "location");
+    let lowest = stages[7]["ranges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|range| range["start"].as_i64().unwrap())
+        .min();
+    assert_eq!(lowest, Some(52210644));
+    // seed ranges are followed apart, as task2 does, so their fragments never merge
+    let fragments: Vec<usize> = stages
+        .iter()
+        .map(|stage| stage["ranges"].as_array().unwrap().len())
+        .collect();
+    assert_eq!(fragments, [10, 15, 20, 34, 52, 68, 89, 109]);
+
+    let output = aoc(&["day5", "export", "--format", "csv"]);
+    assert!(stdout(&output).starts_with("stage,category,kind,seed_range,start,end,offset\n"));
+}
+
+#[test]
+fn day6_report() {
+    let output = aoc(&["day6", "report", "--format", "json"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let races: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let product: u64 = races
+        .iter()
+        .map(|race| race["count"].as_u64().unwrap())
+        .product();
+    assert_eq!(product, 1195150);
+
+    let output = aoc(&["day6", "report", "--friction", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("Race | Time"));
+}
+
+#[test]
+fn day6_report_huge_rate() {
+    let rate = "100000000000000000000000000000000000000";
+    let output = aoc(&["day6", "report", "--rate", rate, "--format", "json"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let races: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(races[0]["winning_min"], 1);
+    assert!(races[0]["margin"].is_null());
+}
+
+#[test]
+fn day6_race_too_long() {
+    let path = temp_input("long-race", "Time: 20000000000000000000\nDistance: 1\n");
+    let output = aoc(&[
+        "--day",
+        "6",
+        "--part",
+        "1",
+        "--input",
+        path.to_str().unwrap(),
+    ]);
+    fs::remove_file(&path).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("race of 20000000000000000000 ms is too long"));
+}
+
+#[test]
+fn day6_parts_parse_on_their_own() {
+    // one race time too many for part 1, a single long race for part 2
+    let path = temp_input("uneven-races", "Time: 7 15\nDistance: 9\n");
+    let run = |part| {
+        aoc(&[
+            "--day",
+            "6",
+            "--part",
+            part,
+            "--input",
+            path.to_str().unwrap(),
+        ])
+    };
+    let part1 = run("1");
+    let part2 = run("2");
+    fs::remove_file(&path).unwrap();
+
+    assert!(!part1.status.success());
+    assert!(stderr(&part1).contains(&format!(
+        "{}:2:12: expected 2 record distances",
+        path.display()
+    )));
+    assert!(part2.status.success(), "{}", stderr(&part2));
+    assert_eq!(answers(&part2), ["714"]);
+}